@@ -0,0 +1,2120 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, Write},
+    path::Path,
+};
+
+use super::cancel::CancellationToken;
+use super::compressor::{window_gains, CompressorOption};
+use super::filter::{Biquad, PreFilterOption};
+use super::limiter::{limit, LimiterOption};
+use super::loudness::{measure, LoudnessMeter, LoudnessReport};
+use super::vad::{VadOption, VoiceActivityDetector};
+use hound;
+use serde::{Deserialize, Serialize};
+use symphonia::core::{
+    audio::{AudioBuffer, AudioBufferRef, Signal},
+    conv::IntoSample,
+    errors::Error as SymphoniaError,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+    sample::Sample,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AudioAnalyzerError {
+    #[error("Failed to read audio file: {0}")]
+    ReadError(String),
+    #[error("Failed to analyze audio file: {0}")]
+    AnalysisError(String),
+    #[error("Failed to decode audio: {0}")]
+    DecodeError(String),
+    #[error("Failed to write audio file: {0}")]
+    WriteError(String),
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+impl From<SymphoniaError> for AudioAnalyzerError {
+    fn from(e: SymphoniaError) -> Self {
+        match e {
+            SymphoniaError::Unsupported(msg) => {
+                AudioAnalyzerError::UnsupportedFormat(msg.to_string())
+            }
+            e => AudioAnalyzerError::DecodeError(e.to_string()),
+        }
+    }
+}
+
+// 이전 버전은 16비트 정수 스케일(-32768..32767)의 RMS와 threshold_db를 비교했다.
+const LEGACY_S16_FULL_SCALE: f32 = 32768.0;
+
+/// 16비트 정수 스케일 기준으로 저장된 기존 threshold_db 값을 dBFS로 변환한다.
+pub fn legacy_threshold_to_dbfs(threshold_db: f32) -> f32 {
+    threshold_db - 20.0 * LEGACY_S16_FULL_SCALE.log10()
+}
+
+/// 모노로 다운믹스된 [-1, 1] 범위의 분석용 샘플
+pub struct ProcessedAudio {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+// 채널을 유지한 채 디코딩한 오디오 (정규화용)
+struct InterleavedAudio {
+    sample_rate: u32,
+    channels: usize,
+    source_format: (u16, hound::SampleFormat),
+    samples: Vec<f32>,
+}
+
+/// 소리 구간 임계값을 정하는 방식
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum ThresholdMode {
+    /// threshold_db를 그대로 사용한다.
+    #[default]
+    Fixed,
+    /// 윈도우별 RMS의 하위 percentile을 노이즈 플로어로 추정하고
+    /// 그보다 margin_db 위에 임계값을 둔다. 디지털 무음인 윈도우는 추정에서 빼며,
+    /// 추정할 윈도우가 없을 때만 threshold_db를 쓴다.
+    /// tracking_sec을 지정하면 해당 길이의 구간마다 노이즈 플로어를 다시 추정해 따라간다.
+    Adaptive {
+        percentile: f32,
+        margin_db: f32,
+        tracking_sec: Option<f32>,
+    },
+}
+
+/// 소리 구간 검출에 사용할 검출기
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum DetectorKind {
+    /// RMS 레벨만으로 판정한다.
+    #[default]
+    Rms,
+    /// RMS 레벨에 음성 대역 에너지 비율과 영교차율 조건을 더해 음성만 판정한다.
+    Vad(VadOption),
+}
+
+impl DetectorKind {
+    pub fn detector(&self) -> Box<dyn SegmentDetector> {
+        match self {
+            DetectorKind::Rms => Box::new(RmsDetector),
+            DetectorKind::Vad(option) => Box::new(VoiceActivityDetector::new(*option)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioAnalyzerOption {
+    pub detector: DetectorKind,
+    pub threshold_mode: ThresholdMode,
+    /// 소리 구간이 시작되는 임계값 (dBFS, 0 이하)
+    pub threshold_db: f32,
+    /// 소리 구간이 끝나는 임계값 (dBFS). None이거나 threshold_db보다 높으면 threshold_db와 같다.
+    /// 적응형 모드에서는 threshold_db와의 차이만큼 적응형 임계값에서 떨어뜨려 적용한다.
+    pub close_threshold_db: Option<f32>,
+    /// threshold_db 이상이 이 시간 동안 유지되어야 구간이 시작된다.
+    pub attack_ms: u32,
+    /// close_threshold_db 미만이 이 시간 동안 유지되어야 구간이 끝난다.
+    pub hold_ms: u32,
+    pub min_duration_ms: u32,
+    /// 이보다 짧은 무음은 자르지 않고 앞뒤 소리 구간을 잇는다.
+    pub min_silence_ms: u32,
+    pub left_buffer_sec: f32,
+    pub right_buffer_sec: f32,
+    /// 버퍼를 더한 구간 사이의 간격이 이 값 이하이면 하나로 병합한다. 겹치는 구간은 항상 병합된다.
+    pub merge_gap_sec: f32,
+    /// RMS를 계산하는 분석 윈도우 길이
+    pub window_ms: u32,
+    /// 분석 윈도우 간격. window_ms보다 작으면 윈도우가 겹친다.
+    pub hop_ms: u32,
+    /// 검출 전에 분석용 샘플에만 적용하는 필터
+    pub pre_filter: PreFilterOption,
+    /// 검출 전에 윈도우 레벨에만 적용하는 컴프레서. 구간 통계도 압축된 레벨 기준이다.
+    pub compressor: Option<CompressorOption>,
+}
+
+impl Default for AudioAnalyzerOption {
+    fn default() -> Self {
+        Self {
+            detector: DetectorKind::Rms,
+            threshold_mode: ThresholdMode::Fixed,
+            threshold_db: -40.0,
+            close_threshold_db: None,
+            attack_ms: 0,
+            hold_ms: 0,
+            min_duration_ms: 50,
+            min_silence_ms: 0,
+            left_buffer_sec: 0.01,
+            right_buffer_sec: 0.15,
+            merge_gap_sec: 0.0,
+            window_ms: 10,
+            hop_ms: 10,
+            pre_filter: PreFilterOption::default(),
+            compressor: None,
+        }
+    }
+}
+
+/// 정규화 기준
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NormalizationMode {
+    /// 최대 진폭을 target_db(dBFS)에 맞춘다.
+    #[default]
+    Peak,
+    /// 전체 RMS를 target_db(dBFS)에 맞춘다.
+    Rms,
+    /// EBU R128 통합 라우드니스를 target_db(LUFS)에 맞춘다.
+    Loudness,
+}
+
+/// 정규화 결과를 저장할 WAV 샘플 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputFormat {
+    /// 원본의 비트 깊이를 유지한다.
+    #[default]
+    Source,
+    Int16,
+    Int24,
+    Float32,
+}
+
+impl OutputFormat {
+    fn resolve(self, source: (u16, hound::SampleFormat)) -> (u16, hound::SampleFormat) {
+        match self {
+            OutputFormat::Source => source,
+            OutputFormat::Int16 => (16, hound::SampleFormat::Int),
+            OutputFormat::Int24 => (24, hound::SampleFormat::Int),
+            OutputFormat::Float32 => (32, hound::SampleFormat::Float),
+        }
+    }
+}
+
+pub struct AudioNormalizerOption {
+    pub target_db: f32,
+    pub mode: NormalizationMode,
+    /// None이면 ±1.0을 넘는 샘플을 잘라낸다.
+    pub limiter: Option<LimiterOption>,
+    pub output_format: OutputFormat,
+}
+
+impl AudioNormalizerOption {
+    /// 정규화한 샘플의 피크 상한 (dBFS). 리미터가 없으면 잘라내는 기준인 0 dBFS다.
+    pub fn ceiling_db(&self) -> f32 {
+        self.limiter
+            .map_or(0.0, |limiter| limiter.ceiling_db.min(0.0))
+    }
+}
+
+impl Default for AudioNormalizerOption {
+    fn default() -> Self {
+        Self {
+            target_db: -3.0,
+            mode: NormalizationMode::Peak,
+            limiter: Some(LimiterOption::default()),
+            output_format: OutputFormat::Source,
+        }
+    }
+}
+
+/// 정규화 전후의 라우드니스 측정 결과
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NormalizationReport {
+    /// 적용한 게인 (dB)
+    pub gain_db: f32,
+    /// 리미터의 최대 게인 감소량 (dB)
+    pub limiter_reduction_db: f32,
+    pub input: LoudnessReport,
+    pub output: LoudnessReport,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f32,
+    pub end: f32,
+    /// 분석 결과로 만들어진 구간에만 채워진다.
+    #[serde(default)]
+    pub stats: Option<SegmentStats>,
+}
+
+impl Segment {
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            stats: None,
+        }
+    }
+}
+
+/// 구간의 음량 통계
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SegmentStats {
+    /// 최대 샘플 레벨 (dBFS)
+    pub peak_db: f32,
+    /// 구간 전체의 RMS 레벨 (dBFS)
+    pub rms_db: f32,
+    /// 윈도우별 RMS 레벨의 평균과 임계값의 차이 (dB)
+    pub mean_relative_db: f32,
+    /// 0 ~ 1. 열림 조건을 만족한 윈도우 비율과 가장 큰 윈도우가 임계값을 넘은 정도로 계산하며,
+    /// 낮을수록 검출이 애매한 구간이다.
+    pub confidence: f32,
+}
+
+/// 타임라인 구간의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegionKind {
+    /// 소리가 검출된 구간
+    Sound,
+    /// 소리 구간은 아니지만 버퍼나 병합으로 남기는 구간
+    Margin,
+    /// 잘라낼 무음 구간
+    Silence,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Region {
+    pub kind: RegionKind,
+    pub start: f32,
+    pub end: f32,
+    pub duration: f32,
+}
+
+impl Region {
+    fn new(kind: RegionKind, start: f32, end: f32) -> Self {
+        Self {
+            kind,
+            start,
+            end,
+            duration: end - start,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionResult {
+    /// 남길 구간 (Sound와 Margin을 합친 범위)
+    pub segments: Vec<Segment>,
+    /// 파일 전체를 빈틈없이 덮는 시간순 구간 목록
+    pub timeline: Vec<Region>,
+    /// 분석한 오디오 길이 (초)
+    pub duration: f32,
+    /// 실제로 적용된 소리 구간 시작 임계값 (dBFS)
+    pub threshold_db: f32,
+    /// 적응형 모드에서 추정한 파일 전체의 노이즈 플로어 (dBFS)
+    pub noise_floor_db: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progress {
+    pub phase: String,
+    pub percentage: f32,
+}
+
+// 샘플 포맷에 관계없이 [-1, 1] 범위의 f32로 변환한 뒤 채널 평균으로 모노 다운믹스
+fn downmix_to_mono<S>(
+    buffer: &AudioBuffer<S>,
+    samples: &mut Vec<f32>,
+) -> Result<usize, AudioAnalyzerError>
+where
+    S: Sample + IntoSample<f32>,
+{
+    let channels = buffer.spec().channels.count();
+    if channels == 0 {
+        return Err(AudioAnalyzerError::UnsupportedFormat(
+            "Audio buffer has no channels".to_string(),
+        ));
+    }
+
+    let frames = buffer.frames();
+    samples.reserve(frames);
+    for i in 0..frames {
+        let mut sum = 0.0;
+        for ch in 0..channels {
+            let sample: f32 = buffer.chan(ch)[i].into_sample();
+            sum += sample;
+        }
+        samples.push(sum / channels as f32);
+    }
+
+    Ok(frames)
+}
+
+// 샘플 포맷에 관계없이 [-1, 1] 범위의 f32로 변환해 채널 순서대로 교차 배치
+fn interleave<S>(buffer: &AudioBuffer<S>, samples: &mut Vec<f32>) -> usize
+where
+    S: Sample + IntoSample<f32>,
+{
+    let channels = buffer.spec().channels.count();
+    let frames = buffer.frames();
+    samples.reserve(frames * channels);
+    for i in 0..frames {
+        for ch in 0..channels {
+            samples.push(buffer.chan(ch)[i].into_sample());
+        }
+    }
+
+    frames
+}
+
+// 원본의 샘플 포맷에 맞는 WAV 비트 깊이.
+// FLAC처럼 디코딩 버퍼가 원본보다 넓을 수 있으므로 코덱이 알려준 비트 깊이를 먼저 쓴다.
+fn source_sample_format(
+    buffer: &AudioBufferRef,
+    bits_per_sample: Option<u32>,
+) -> (u16, hound::SampleFormat) {
+    let is_float = matches!(buffer, AudioBufferRef::F32(_) | AudioBufferRef::F64(_));
+    match (is_float, bits_per_sample) {
+        // WAV에는 64비트 실수를 쓰지 않으므로 32비트 실수로 저장한다.
+        (true, Some(_)) => (32, hound::SampleFormat::Float),
+        // 비트 깊이가 없는 손실 압축 원본은 16비트 정수로 저장한다.
+        (true, None) => (16, hound::SampleFormat::Int),
+        (false, Some(bits)) => {
+            let bits = match bits {
+                0..=8 => 8,
+                9..=16 => 16,
+                17..=24 => 24,
+                _ => 32,
+            };
+            (bits, hound::SampleFormat::Int)
+        }
+        (false, None) => match buffer {
+            AudioBufferRef::U8(_) | AudioBufferRef::S8(_) => (8, hound::SampleFormat::Int),
+            AudioBufferRef::U16(_) | AudioBufferRef::S16(_) => (16, hound::SampleFormat::Int),
+            AudioBufferRef::U24(_) | AudioBufferRef::S24(_) => (24, hound::SampleFormat::Int),
+            _ => (32, hound::SampleFormat::Int),
+        },
+    }
+}
+
+pub(crate) fn create_wav(
+    path: &str,
+    spec: hound::WavSpec,
+) -> Result<hound::WavWriter<BufWriter<File>>, AudioAnalyzerError> {
+    hound::WavWriter::create(path, spec).map_err(|e| AudioAnalyzerError::WriteError(e.to_string()))
+}
+
+// [-1, 1] 범위의 교차 배치 샘플을 writer의 형식에 맞춰 이어 쓴다.
+pub(crate) fn write_samples<W: Write + Seek>(
+    writer: &mut hound::WavWriter<W>,
+    samples: &[f32],
+) -> Result<(), AudioAnalyzerError> {
+    let spec = writer.spec();
+    let max = ((1_i64 << (spec.bits_per_sample - 1)) - 1) as f64;
+    for &sample in samples {
+        let result = match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Float, _) => writer.write_sample(sample),
+            (hound::SampleFormat::Int, 8) => {
+                writer.write_sample((sample as f64 * max).round() as i8)
+            }
+            (hound::SampleFormat::Int, 16) => {
+                writer.write_sample((sample as f64 * max).round() as i16)
+            }
+            (hound::SampleFormat::Int, _) => {
+                writer.write_sample((sample as f64 * max).round() as i32)
+            }
+        };
+        result.map_err(|e| AudioAnalyzerError::WriteError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn finalize_wav<W: Write + Seek>(
+    writer: hound::WavWriter<W>,
+) -> Result<(), AudioAnalyzerError> {
+    writer
+        .finalize()
+        .map_err(|e| AudioAnalyzerError::WriteError(e.to_string()))
+}
+
+// [-1, 1] 범위의 교차 배치 샘플을 WAV 파일로 저장
+pub(crate) fn write_wav(
+    path: &str,
+    spec: hound::WavSpec,
+    samples: &[f32],
+) -> Result<(), AudioAnalyzerError> {
+    let mut writer = create_wav(path, spec)?;
+    write_samples(&mut writer, samples)?;
+    finalize_wav(writer)
+}
+
+// 몇 분 단위의 값도 u32 곱셈에서 넘치지 않도록 u64로 계산한다.
+fn ms_to_samples(sample_rate: u32, ms: u32) -> usize {
+    (sample_rate as u64 * ms as u64 / 1000) as usize
+}
+
+// 무음(0)에서 -inf가 되지 않도록 하한을 둔다.
+const MIN_LEVEL_DB: f32 = -100.0;
+
+fn amplitude_to_db(amplitude: f32) -> f32 {
+    (20.0 * amplitude.log10()).max(MIN_LEVEL_DB)
+}
+
+fn db_to_amplitude(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+// values는 순서가 바뀔 수 있다.
+fn percentile(values: &mut [f32], percentile: f32) -> f32 {
+    if values.is_empty() {
+        return MIN_LEVEL_DB;
+    }
+
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (values.len() - 1) as f32).round();
+    let (_, value, _) = values.select_nth_unstable_by(rank as usize, |a, b| a.total_cmp(b));
+    *value
+}
+
+// 윈도우별 RMS로부터 노이즈 플로어(dBFS)를 추정한다.
+// 0으로 채운 구간처럼 MIN_LEVEL_DB 이하인 윈도우는 빼고, 남는 윈도우가 없으면 None을 돌려준다.
+fn estimate_noise_floor(rms_values: &[f32], percentile_rank: f32) -> Option<f32> {
+    let mut levels: Vec<f32> = rms_values
+        .iter()
+        .map(|&rms| amplitude_to_db(rms))
+        .filter(|&level| level > MIN_LEVEL_DB)
+        .collect();
+    if levels.is_empty() {
+        return None;
+    }
+    Some(percentile(&mut levels, percentile_rank))
+}
+
+// tracking_windows 길이의 블록마다 노이즈 플로어를 추정하고,
+// 블록 중심 사이를 선형 보간해 윈도우별 노이즈 플로어(dBFS)를 만든다.
+// 추정할 수 없는 블록은 fallback_db를 쓴다.
+fn track_noise_floor(
+    rms_values: &[f32],
+    percentile_rank: f32,
+    tracking_windows: usize,
+    fallback_db: f32,
+) -> Vec<f32> {
+    let tracking_windows = tracking_windows.max(1);
+    let floors: Vec<f32> = rms_values
+        .chunks(tracking_windows)
+        .map(|block| estimate_noise_floor(block, percentile_rank).unwrap_or(fallback_db))
+        .collect();
+    let centers: Vec<f32> = rms_values
+        .chunks(tracking_windows)
+        .enumerate()
+        .map(|(i, block)| (i * tracking_windows) as f32 + block.len() as f32 / 2.0)
+        .collect();
+
+    let mut block = 0;
+    (0..rms_values.len())
+        .map(|i| {
+            let position = i as f32 + 0.5;
+            while block + 1 < centers.len() && centers[block + 1] <= position {
+                block += 1;
+            }
+
+            if block + 1 >= centers.len() || position <= centers[block] {
+                return floors[block];
+            }
+
+            let ratio = (position - centers[block]) / (centers[block + 1] - centers[block]);
+            floors[block] + (floors[block + 1] - floors[block]) * ratio
+        })
+        .collect()
+}
+
+// 샘플 범위로 표현된 소리 구간 사이의 무음이 min_silence_samples보다 짧으면 하나로 잇는다.
+// 맞닿은 구간은 항상 잇는다.
+fn bridge_short_silences(
+    regions: Vec<(usize, usize)>,
+    min_silence_samples: usize,
+) -> Vec<(usize, usize)> {
+    let mut bridged: Vec<(usize, usize)> = Vec::with_capacity(regions.len());
+    for (start, end) in regions {
+        match bridged.last_mut() {
+            Some(last) if start.saturating_sub(last.1) < min_silence_samples.max(1) => {
+                last.1 = last.1.max(end);
+            }
+            _ => bridged.push((start, end)),
+        }
+    }
+    bridged
+}
+
+// 구간을 [0, duration] 범위로 자르고 시작 시간 순으로 정렬한 뒤,
+// 겹치거나 간격이 merge_gap_sec 이하인 구간을 병합한다.
+// 결과는 정렬되어 있고 서로 겹치지 않는다.
+fn merge_segments(mut segments: Vec<Segment>, merge_gap_sec: f32, duration: f32) -> Vec<Segment> {
+    for segment in &mut segments {
+        segment.start = segment.start.clamp(0.0, duration);
+        segment.end = segment.end.clamp(0.0, duration);
+    }
+    segments.retain(|segment| segment.end > segment.start);
+    segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if segment.start <= last.end + merge_gap_sec.max(0.0) => {
+                last.end = last.end.max(segment.end);
+            }
+            _ => merged.push(segment),
+        }
+    }
+    merged
+}
+
+// 정렬되고 겹치지 않는 소리 구간(sounds)과 남길 구간(segments)으로
+// 파일 전체를 Sound / Margin / Silence 구간으로 나눈다.
+fn build_timeline(sounds: &[Segment], segments: &[Segment], duration: f32) -> Vec<Region> {
+    let mut timeline = Vec::with_capacity(sounds.len() * 3 + 1);
+    let mut sounds = sounds.iter().peekable();
+    let mut cursor = 0.0;
+
+    for segment in segments {
+        if segment.start > cursor {
+            timeline.push(Region::new(RegionKind::Silence, cursor, segment.start));
+        }
+
+        let mut position = segment.start;
+        while let Some(sound) = sounds.next_if(|sound| sound.start < segment.end) {
+            let start = sound.start.max(segment.start);
+            let end = sound.end.min(segment.end);
+            if start > position {
+                timeline.push(Region::new(RegionKind::Margin, position, start));
+            }
+            if end > start {
+                timeline.push(Region::new(RegionKind::Sound, start, end));
+                position = end;
+            }
+        }
+        if segment.end > position {
+            timeline.push(Region::new(RegionKind::Margin, position, segment.end));
+        }
+
+        cursor = segment.end;
+    }
+
+    if duration > cursor {
+        timeline.push(Region::new(RegionKind::Silence, cursor, duration));
+    }
+
+    timeline
+}
+
+// 임계값보다 이만큼 큰 윈도우가 있으면 레벨 측면의 신뢰도를 최대로 본다.
+const CONFIDENCE_RANGE_DB: f32 = 20.0;
+
+// 시작 위치가 [start_sample, end_sample) 안에 있는 윈도우들로 구간 통계를 계산한다.
+fn segment_stats(
+    levels: &WindowLevels,
+    open: &[bool],
+    (start_sample, end_sample): (usize, usize),
+    threshold_db: f32,
+) -> SegmentStats {
+    let first = start_sample.div_ceil(levels.window.hop);
+    let last = end_sample.div_ceil(levels.window.hop).min(levels.rms.len());
+    let rms_values = &levels.rms[first.min(last)..last];
+    let open = &open[first.min(last)..last];
+    let peak = levels.peak[first.min(last)..last]
+        .iter()
+        .fold(0.0_f32, |peak, &sample| peak.max(sample));
+    if rms_values.is_empty() {
+        return SegmentStats {
+            peak_db: amplitude_to_db(peak),
+            rms_db: MIN_LEVEL_DB,
+            mean_relative_db: MIN_LEVEL_DB - threshold_db,
+            confidence: 0.0,
+        };
+    }
+
+    let count = rms_values.len() as f32;
+    let mean_square = rms_values.iter().map(|&rms| rms * rms).sum::<f32>() / count;
+    let mean_level_db = rms_values
+        .iter()
+        .map(|&rms| amplitude_to_db(rms))
+        .sum::<f32>()
+        / count;
+    let loudest_db = amplitude_to_db(rms_values.iter().fold(0.0_f32, |a, &b| a.max(b)));
+    let open_ratio = open.iter().filter(|&&open| open).count() as f32 / count;
+
+    SegmentStats {
+        peak_db: amplitude_to_db(peak),
+        rms_db: amplitude_to_db(mean_square.sqrt()),
+        mean_relative_db: mean_level_db - threshold_db,
+        confidence: open_ratio
+            * ((loudest_db - threshold_db) / CONFIDENCE_RANGE_DB).clamp(0.0, 1.0),
+    }
+}
+
+/// 분석 윈도우의 길이와 간격 (샘플 단위)
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisWindow {
+    pub size: usize,
+    pub hop: usize,
+}
+
+impl AnalysisWindow {
+    // 간격이 윈도우보다 길면 분석되지 않는 샘플이 생기므로 윈도우 길이로 제한한다.
+    pub fn new(sample_rate: u32, window_ms: u32, hop_ms: u32) -> Self {
+        let size = ms_to_samples(sample_rate, window_ms).max(1);
+        let hop = ms_to_samples(sample_rate, hop_ms).clamp(1, size);
+        Self { size, hop }
+    }
+
+    pub fn count(&self, sample_count: usize) -> usize {
+        if sample_count == 0 {
+            0
+        } else {
+            (sample_count - 1) / self.hop + 1
+        }
+    }
+
+    pub fn start(&self, index: usize) -> usize {
+        index * self.hop
+    }
+
+    /// index번째 윈도우의 끝 샘플 위치 (파일 끝을 넘지 않는다)
+    pub fn end(&self, index: usize, sample_count: usize) -> usize {
+        (index * self.hop + self.size).min(sample_count)
+    }
+
+    /// index번째 윈도우가 대표하는 샘플 범위. 윈도우 중심을 기준으로 hop 길이만큼이며,
+    /// 윈도우가 겹쳐도 이웃한 윈도우의 범위와 겹치지 않는다. 처음과 마지막 윈도우는 파일 끝까지 늘린다.
+    pub fn span(&self, index: usize, sample_count: usize) -> (usize, usize) {
+        let offset = (self.size - self.hop) / 2;
+        let start = if index == 0 {
+            0
+        } else {
+            index * self.hop + offset
+        };
+        let end = if index + 1 >= self.count(sample_count) {
+            sample_count
+        } else {
+            ((index + 1) * self.hop + offset).min(sample_count)
+        };
+        (start.min(end), end)
+    }
+}
+
+/// 블록 단위로 들어오는 샘플을 hop 간격의 윈도우로 나눈다.
+/// 아직 끝나지 않은 윈도우 분량의 샘플만 남긴다.
+pub struct WindowBuffer<T> {
+    window: AnalysisWindow,
+    buffer: Vec<T>,
+    // buffer[0]의 샘플 위치
+    offset: usize,
+    // 다음에 넘길 윈도우 번호
+    next: usize,
+}
+
+impl<T: Copy> WindowBuffer<T> {
+    pub fn new(window: AnalysisWindow) -> Self {
+        Self {
+            window,
+            buffer: Vec::with_capacity(window.size),
+            offset: 0,
+            next: 0,
+        }
+    }
+
+    /// 샘플을 이어 붙이고, 끝까지 채워진 윈도우를 차례로 on_window에 넘긴다.
+    pub fn push(&mut self, samples: &[T], mut on_window: impl FnMut(&[T])) {
+        self.buffer.extend_from_slice(samples);
+        loop {
+            let start = self.window.start(self.next) - self.offset;
+            let end = start + self.window.size;
+            if end > self.buffer.len() {
+                break;
+            }
+            on_window(&self.buffer[start..end]);
+            self.next += 1;
+        }
+
+        let consumed = self.window.start(self.next) - self.offset;
+        self.buffer.drain(..consumed);
+        self.offset += consumed;
+    }
+
+    /// 입력이 끝난 뒤 파일 끝에서 잘린 짧은 윈도우들을 넘긴다.
+    pub fn finish(&mut self, mut on_window: impl FnMut(&[T])) {
+        while self.window.start(self.next) < self.offset + self.buffer.len() {
+            on_window(&self.buffer[self.window.start(self.next) - self.offset..]);
+            self.next += 1;
+        }
+    }
+}
+
+/// 검출기가 판정에 쓰는 윈도우별 레벨.
+/// 정규화 게인과 컴프레서가 반영되어 있다.
+pub struct WindowLevels {
+    pub sample_rate: u32,
+    pub window: AnalysisWindow,
+    /// 윈도우별 RMS (진폭)
+    pub rms: Vec<f32>,
+    /// 윈도우별 최대 절대값
+    pub peak: Vec<f32>,
+}
+
+impl WindowLevels {
+    fn push(&mut self, chunk: &[f32]) {
+        let sum_squares: f32 = chunk.iter().map(|&s| s * s).sum();
+        self.rms.push((sum_squares / chunk.len() as f32).sqrt());
+        self.peak
+            .push(chunk.iter().fold(0.0_f32, |peak, &s| peak.max(s.abs())));
+    }
+
+    // 블록을 모두 받은 뒤에 정해진 정규화 게인과 컴프레서를 반영한다.
+    // 리미터(없으면 클리핑)는 상한을 넘는 피크만큼 그 윈도우의 레벨을 줄이는 것으로 본다.
+    fn adjust(
+        &mut self,
+        gain_db: f32,
+        ceiling_db: Option<f32>,
+        compressor: Option<CompressorOption>,
+    ) {
+        let gain = db_to_amplitude(gain_db);
+        let ceiling = ceiling_db.map(db_to_amplitude);
+        for (rms, peak) in self.rms.iter_mut().zip(self.peak.iter_mut()) {
+            *rms *= gain;
+            *peak *= gain;
+            if let Some(ceiling) = ceiling.filter(|&ceiling| *peak > ceiling) {
+                *rms *= ceiling / *peak;
+                *peak = ceiling;
+            }
+        }
+
+        if let Some(compressor) = compressor {
+            let windows_per_sec = self.sample_rate as f32 / self.window.hop as f32;
+            let gains = window_gains(&self.peak, windows_per_sec, compressor);
+            for ((rms, peak), gain) in self.rms.iter_mut().zip(self.peak.iter_mut()).zip(gains) {
+                *rms *= gain;
+                *peak *= gain;
+            }
+        }
+    }
+}
+
+// 닫힘 임계값은 열림 임계값에 대한 비율로 적용한다.
+// 열림 임계값보다 높으면 같은 윈도우에서 구간이 열리고 바로 닫히므로 열림 임계값으로 제한한다.
+pub(crate) fn close_ratio(option: &AudioAnalyzerOption) -> f32 {
+    let close_threshold_db = option
+        .close_threshold_db
+        .map_or(option.threshold_db, |close_db| {
+            close_db.min(option.threshold_db)
+        });
+    db_to_amplitude(close_threshold_db - option.threshold_db)
+}
+
+// threshold_mode에 따라 적용할 임계값(dBFS), 노이즈 플로어, 윈도우별 열림 임계값(진폭)을 구한다.
+pub(crate) fn resolve_open_amplitudes(
+    rms_values: &[f32],
+    sample_rate: u32,
+    window: AnalysisWindow,
+    option: &AudioAnalyzerOption,
+) -> (f32, Option<f32>, Vec<f32>) {
+    let rms_len = rms_values.len();
+    match option.threshold_mode {
+        ThresholdMode::Fixed => (
+            option.threshold_db,
+            None,
+            vec![db_to_amplitude(option.threshold_db); rms_len],
+        ),
+        ThresholdMode::Adaptive {
+            percentile,
+            margin_db,
+            tracking_sec,
+        } => {
+            let noise_floor_db = estimate_noise_floor(rms_values, percentile);
+            // 노이즈 플로어를 추정할 수 없으면 임계값이 threshold_db가 되도록 한다.
+            let fallback_db = option.threshold_db - margin_db;
+            let floor_db = noise_floor_db.unwrap_or(fallback_db);
+            let open_amplitudes = match tracking_sec {
+                Some(tracking_sec) => {
+                    let tracking_windows =
+                        (tracking_sec * sample_rate as f32 / window.hop as f32) as usize;
+                    track_noise_floor(rms_values, percentile, tracking_windows, fallback_db)
+                        .into_iter()
+                        .map(|floor_db| db_to_amplitude(floor_db + margin_db))
+                        .collect()
+                }
+                None => vec![db_to_amplitude(floor_db + margin_db); rms_len],
+            };
+            (floor_db + margin_db, noise_floor_db, open_amplitudes)
+        }
+    }
+}
+
+/// 윈도우별 판정 결과
+pub struct WindowActivity {
+    /// 소리 구간을 시작할 수 있는 윈도우인지
+    pub open: Vec<bool>,
+    /// 소리 구간을 끝낼 수 있는 윈도우인지
+    pub close: Vec<bool>,
+    /// 실제로 적용된 소리 구간 시작 임계값 (dBFS)
+    pub threshold_db: f32,
+    pub noise_floor_db: Option<f32>,
+}
+
+/// 윈도우 단위로 소리 구간 여부를 판정하는 검출기.
+/// attack/hold, 최소 길이, 버퍼, 병합은 검출기와 관계없이 AudioAnalyzer가 처리한다.
+pub trait SegmentDetector {
+    /// 분석을 시작할 때 불린다. 이전 분석에서 모은 상태는 여기서 비운다.
+    fn begin(&mut self, _sample_rate: u32, _window: AnalysisWindow) {}
+
+    /// 전처리 필터를 거친 샘플을 블록 단위로 차례로 받는다.
+    /// 정규화 게인은 블록을 모두 받은 뒤에 정해지므로 레벨과 관계없는 특징만 모아야 한다.
+    fn observe(&mut self, _samples: &[f32]) {}
+
+    /// 마지막 블록을 받은 뒤에 불린다.
+    fn finish(&mut self) {}
+
+    fn classify(&self, levels: &WindowLevels, option: &AudioAnalyzerOption) -> WindowActivity;
+}
+
+/// RMS 레벨을 임계값과 비교하는 기본 검출기
+pub struct RmsDetector;
+
+impl SegmentDetector for RmsDetector {
+    fn classify(&self, levels: &WindowLevels, option: &AudioAnalyzerOption) -> WindowActivity {
+        let (threshold_db, noise_floor_db, open_amplitudes) =
+            resolve_open_amplitudes(&levels.rms, levels.sample_rate, levels.window, option);
+        let close_ratio = close_ratio(option);
+
+        WindowActivity {
+            open: levels
+                .rms
+                .iter()
+                .zip(&open_amplitudes)
+                .map(|(&rms, &open_amplitude)| rms >= open_amplitude)
+                .collect(),
+            close: levels
+                .rms
+                .iter()
+                .zip(&open_amplitudes)
+                .map(|(&rms, &open_amplitude)| rms < open_amplitude * close_ratio)
+                .collect(),
+            threshold_db,
+            noise_floor_db,
+        }
+    }
+}
+
+/// 모노 샘플을 블록 단위로 받아 소리 구간을 찾는다.
+/// 샘플은 윈도우 하나 분량만 남기고 윈도우별 레벨만 모으므로, 긴 파일도 샘플 전체를 메모리에 올리지 않는다.
+pub struct SegmentStream<'a> {
+    option: AudioAnalyzerOption,
+    detector: &'a mut dyn SegmentDetector,
+    filters: Vec<Biquad>,
+    windows: WindowBuffer<f32>,
+    levels: WindowLevels,
+    filtered: Vec<f32>,
+    sample_count: usize,
+}
+
+impl<'a> SegmentStream<'a> {
+    pub fn new(
+        sample_rate: u32,
+        option: AudioAnalyzerOption,
+        detector: &'a mut dyn SegmentDetector,
+    ) -> Self {
+        let window = AnalysisWindow::new(sample_rate, option.window_ms, option.hop_ms);
+        detector.begin(sample_rate, window);
+
+        Self {
+            option,
+            detector,
+            filters: option.pre_filter.filters(sample_rate),
+            windows: WindowBuffer::new(window),
+            levels: WindowLevels {
+                sample_rate,
+                window,
+                rms: Vec::new(),
+                peak: Vec::new(),
+            },
+            filtered: Vec::new(),
+            sample_count: 0,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        let Self {
+            detector,
+            filters,
+            windows,
+            levels,
+            filtered,
+            ..
+        } = self;
+
+        // 원본은 건드리지 않고 분석용 샘플에만 필터를 적용한다.
+        filtered.clear();
+        filtered.extend(samples.iter().map(|&sample| {
+            filters
+                .iter_mut()
+                .fold(sample, |value, filter| filter.process(value))
+        }));
+
+        detector.observe(filtered);
+        windows.push(filtered, |chunk| levels.push(chunk));
+        self.sample_count += samples.len();
+    }
+
+    /// 블록을 모두 받은 뒤 소리 구간을 찾는다.
+    /// gain_db는 그 사이에 정해진 정규화 게인이고, ceiling_db는 정규화한 샘플의 피크 상한이다.
+    pub fn finish<F>(
+        self,
+        gain_db: f32,
+        ceiling_db: Option<f32>,
+        cancel: &CancellationToken,
+        progress_callback: &mut F,
+    ) -> Result<DetectionResult, AudioAnalyzerError>
+    where
+        F: FnMut(Progress),
+    {
+        let Self {
+            option,
+            detector,
+            mut windows,
+            mut levels,
+            sample_count,
+            ..
+        } = self;
+        let AudioAnalyzerOption {
+            attack_ms,
+            hold_ms,
+            min_duration_ms,
+            min_silence_ms,
+            left_buffer_sec,
+            right_buffer_sec,
+            merge_gap_sec,
+            ..
+        } = option;
+        let WindowLevels {
+            sample_rate,
+            window,
+            ..
+        } = levels;
+
+        windows.finish(|chunk| levels.push(chunk));
+        detector.finish();
+        levels.adjust(gain_db, ceiling_db, option.compressor);
+
+        let left_buffer_samples = (sample_rate as f32 * left_buffer_sec) as usize;
+        let right_buffer_samples = (sample_rate as f32 * right_buffer_sec) as usize;
+
+        let min_samples = ms_to_samples(sample_rate, min_duration_ms);
+        let min_silence_samples = ms_to_samples(sample_rate, min_silence_ms);
+        let attack_samples = ms_to_samples(sample_rate, attack_ms);
+        let hold_samples = ms_to_samples(sample_rate, hold_ms);
+
+        // 윈도우 레벨 계산 완료 (40%)
+        progress_callback(Progress {
+            phase: "Analyzing Audio".to_string(),
+            percentage: 40.0,
+        });
+
+        let rms_len = levels.rms.len();
+        let WindowActivity {
+            open,
+            close,
+            threshold_db,
+            noise_floor_db,
+        } = detector.classify(&levels, &option);
+
+        let mut regions = Vec::new();
+
+        // start_idx부터 end_idx 직전까지의 윈도우가 대표하는 샘플 범위를 소리 구간으로 추가한다.
+        // 겹치는 윈도우의 전체 범위를 쓰면 구간이 양쪽으로 (윈도우 - hop) 만큼 넓어진다.
+        let mut push_region = |start_idx: usize, end_idx: usize| {
+            regions.push((
+                window.span(start_idx, sample_count).0,
+                window.span(end_idx - 1, sample_count).1,
+            ));
+        };
+
+        // 열림/닫힘 조건을 분리한 히스테리시스 방식으로 구간을 검출한다.
+        // 열림 조건이 attack 동안 유지되면 구간이 시작되고,
+        // 닫힘 조건이 hold 동안 유지되면 처음 닫힘 조건이 된 위치에서 구간이 끝난다.
+        let mut is_non_silent = false;
+        let mut segment_start_idx = 0;
+        let mut above_windows = 0;
+        let mut below_windows = 0;
+
+        // 세그먼트 검색 진행률 업데이트 (40 ~ 80%)
+        for i in 0..rms_len {
+            if !is_non_silent {
+                if open[i] {
+                    above_windows += 1;
+                    if above_windows * window.hop >= attack_samples {
+                        is_non_silent = true;
+                        segment_start_idx = i + 1 - above_windows;
+                        below_windows = 0;
+                    }
+                } else {
+                    above_windows = 0;
+                }
+            } else if close[i] {
+                below_windows += 1;
+                if below_windows * window.hop >= hold_samples {
+                    is_non_silent = false;
+                    above_windows = 0;
+                    push_region(segment_start_idx, i + 1 - below_windows);
+                }
+            } else {
+                below_windows = 0;
+            }
+
+            // 세그먼트 검색 진행률 업데이트
+            if i % (rms_len / 20).max(1) == 0 {
+                if cancel.is_cancelled() {
+                    return Err(AudioAnalyzerError::Cancelled);
+                }
+                let progress = 40.0 + (i as f32 / rms_len as f32) * 40.0;
+                progress_callback(Progress {
+                    phase: "Analyzing Audio".to_string(),
+                    percentage: progress,
+                });
+            }
+        }
+
+        if is_non_silent {
+            push_region(segment_start_idx, rms_len - below_windows);
+        }
+
+        // 짧은 무음을 이은 뒤 최소 길이를 넘는 구간에만 버퍼를 더한다.
+        let sounds: Vec<Segment> = bridge_short_silences(regions, min_silence_samples)
+            .into_iter()
+            .filter(|&(start, end)| end - start >= min_samples)
+            .map(|(start, end)| {
+                Segment::new(
+                    start as f32 / sample_rate as f32,
+                    end as f32 / sample_rate as f32,
+                )
+            })
+            .collect();
+
+        // 소리 구간에 버퍼를 더한다. 파일 범위를 넘는 부분은 병합 단계에서 잘린다.
+        let left_buffer_sec = left_buffer_samples as f32 / sample_rate as f32;
+        let right_buffer_sec = right_buffer_samples as f32 / sample_rate as f32;
+        let segments: Vec<Segment> = sounds
+            .iter()
+            .map(|sound| Segment::new(sound.start - left_buffer_sec, sound.end + right_buffer_sec))
+            .collect();
+
+        // 세그먼트 검색 완료 (80%)
+        progress_callback(Progress {
+            phase: "Analyzing Audio".to_string(),
+            percentage: 80.0,
+        });
+
+        let duration = sample_count as f32 / sample_rate as f32;
+        let segments = merge_segments(segments, merge_gap_sec, duration);
+        let timeline = build_timeline(&sounds, &segments, duration);
+        let segments = segments
+            .into_iter()
+            .map(|segment| {
+                let start_sample = (segment.start * sample_rate as f32) as usize;
+                let end_sample = ((segment.end * sample_rate as f32) as usize).min(sample_count);
+                Segment {
+                    stats: Some(segment_stats(
+                        &levels,
+                        &open,
+                        (start_sample.min(end_sample), end_sample),
+                        threshold_db,
+                    )),
+                    ..segment
+                }
+            })
+            .collect();
+
+        // 완료 (100%)
+        progress_callback(Progress {
+            phase: "Analyzing Audio".to_string(),
+            percentage: 100.0,
+        });
+
+        Ok(DetectionResult {
+            segments,
+            timeline,
+            duration,
+            threshold_db,
+            noise_floor_db,
+        })
+    }
+}
+
+/// 블록 단위로 교차 배치 샘플을 받아 정규화 게인을 정한다.
+pub struct NormalizationMeter {
+    target_db: f32,
+    mode: NormalizationMode,
+    has_limiter: bool,
+    ceiling_db: f32,
+    loudness: LoudnessMeter,
+    peak: f32,
+    sum_squares: f64,
+    sample_count: usize,
+}
+
+impl NormalizationMeter {
+    pub fn new(sample_rate: u32, channels: usize, option: &AudioNormalizerOption) -> Self {
+        Self {
+            target_db: option.target_db,
+            mode: option.mode,
+            has_limiter: option.limiter.is_some(),
+            ceiling_db: option.ceiling_db(),
+            loudness: LoudnessMeter::new(sample_rate, channels),
+            peak: 0.0,
+            sum_squares: 0.0,
+            sample_count: 0,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        self.loudness.process_interleaved(samples);
+        for &sample in samples {
+            self.peak = self.peak.max(sample.abs());
+            self.sum_squares += sample as f64 * sample as f64;
+        }
+        self.sample_count += samples.len();
+    }
+
+    // 입력의 라우드니스와 정규화 계수
+    fn measure(&self) -> (LoudnessReport, f32) {
+        let input = self.loudness.report();
+        let target_amplitude = db_to_amplitude(self.target_db);
+        let rms = (self.sum_squares / self.sample_count.max(1) as f64).sqrt() as f32;
+
+        let factor = match self.mode {
+            // 모든 채널 중 최대 진폭을 기준으로 정규화
+            NormalizationMode::Peak if self.peak > 0.0 => target_amplitude / self.peak,
+            // 모든 채널의 RMS 값을 기준으로 정규화
+            NormalizationMode::Rms if rms > 0.0 => target_amplitude / rms,
+            // 통합 라우드니스를 기준으로 정규화 (게이트를 모두 통과하지 못하면 그대로 둔다)
+            NormalizationMode::Loudness if input.integrated_lufs.is_finite() => {
+                db_to_amplitude(self.target_db - input.integrated_lufs)
+            }
+            _ => 1.0,
+        };
+        (input, factor)
+    }
+
+    /// 게인을 적용한 결과를 입력 측정값으로 추정한다.
+    /// 리미터는 트루 피크가 상한을 넘는 만큼만 게인을 줄인 것으로 본다.
+    pub fn report(&self) -> NormalizationReport {
+        let (input, factor) = self.measure();
+        let gain_db = 20.0 * factor.log10();
+        let ceiling_db = self.ceiling_db;
+
+        NormalizationReport {
+            gain_db,
+            limiter_reduction_db: if self.has_limiter {
+                (input.true_peak_db + gain_db - ceiling_db).max(0.0)
+            } else {
+                0.0
+            },
+            input,
+            output: LoudnessReport {
+                integrated_lufs: input.integrated_lufs + gain_db,
+                max_momentary_lufs: input.max_momentary_lufs + gain_db,
+                max_short_term_lufs: input.max_short_term_lufs + gain_db,
+                loudness_range_lu: input.loudness_range_lu,
+                sample_peak_db: (input.sample_peak_db + gain_db).min(ceiling_db),
+                true_peak_db: (input.true_peak_db + gain_db).min(ceiling_db),
+            },
+        }
+    }
+}
+
+pub struct AudioAnalyzer {}
+
+impl AudioAnalyzer {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    // 기본 트랙의 패킷을 모두 디코딩하며 버퍼마다 handle_buffer를 호출하고, 샘플레이트를 돌려준다.
+    // handle_buffer는 코덱이 알려준 원본 비트 깊이도 함께 받고, 처리한 프레임 수를 돌려준다.
+    fn decode_audio<F, H>(
+        &self,
+        audio_path: &str,
+        cancel: &CancellationToken,
+        progress_callback: &mut F,
+        mut handle_buffer: H,
+    ) -> Result<u32, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+        H: FnMut(AudioBufferRef, Option<u32>) -> Result<usize, AudioAnalyzerError>,
+    {
+        progress_callback(Progress {
+            phase: "Processing Audio".to_string(),
+            percentage: 0.0,
+        });
+
+        let file =
+            File::open(&audio_path).map_err(|e| AudioAnalyzerError::ReadError(e.to_string()))?;
+        let media_source = MediaSourceStream::new(Box::new(file), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = Path::new(&audio_path).extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let format_opts = FormatOptions {
+            enable_gapless: true,
+            ..Default::default()
+        };
+        let metadata_opts = MetadataOptions::default();
+        let probed = symphonia::default::get_probe().format(
+            &hint,
+            media_source,
+            &format_opts,
+            &metadata_opts,
+        )?;
+
+        let mut format = probed.format;
+        let track = format
+            .default_track()
+            .ok_or(AudioAnalyzerError::AnalysisError(
+                "No default track".to_string(),
+            ))?;
+        let track_id = track.id;
+        let bits_per_sample = track.codec_params.bits_per_sample;
+
+        let mut decoder =
+            symphonia::default::get_codecs().make(&track.codec_params, &Default::default())?;
+        let mut sample_rate = 0;
+
+        // 프로그레스 출력을 위한 변수
+        let total_frames = decoder.codec_params().n_frames;
+        let mut processed_frames = 0;
+
+        while let Ok(packet) = format.next_packet() {
+            if cancel.is_cancelled() {
+                return Err(AudioAnalyzerError::Cancelled);
+            }
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let decoded = decoder.decode(&packet)?;
+
+            if sample_rate == 0 {
+                sample_rate = decoded.spec().rate;
+            }
+
+            processed_frames += handle_buffer(decoded, bits_per_sample)?;
+
+            if let Some(total_frames) = total_frames {
+                progress_callback(Progress {
+                    phase: "Processing Audio".to_string(),
+                    percentage: processed_frames as f32 / total_frames as f32 * 100.0,
+                });
+            }
+        }
+
+        if sample_rate == 0 {
+            return Err(AudioAnalyzerError::UnsupportedFormat(
+                "No decodable audio samples".to_string(),
+            ));
+        }
+
+        Ok(sample_rate)
+    }
+
+    // 다운믹스하지 않고 채널 수와 비트 깊이를 유지한 채 디코딩
+    fn process_interleaved_samples<F>(
+        &self,
+        audio_path: &str,
+        cancel: &CancellationToken,
+        progress_callback: &mut F,
+    ) -> Result<InterleavedAudio, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+    {
+        let mut samples: Vec<f32> = Vec::new();
+        let mut channels = 0;
+        let mut source_format = None;
+
+        let sample_rate = self.decode_audio(
+            audio_path,
+            cancel,
+            progress_callback,
+            |decoded, bits_per_sample| {
+                let buffer_channels = decoded.spec().channels.count();
+                if buffer_channels == 0 || (channels != 0 && buffer_channels != channels) {
+                    return Err(AudioAnalyzerError::UnsupportedFormat(format!(
+                        "Unexpected channel count: {}",
+                        buffer_channels
+                    )));
+                }
+                channels = buffer_channels;
+                source_format.get_or_insert(source_sample_format(&decoded, bits_per_sample));
+
+                Ok(match decoded {
+                    AudioBufferRef::U8(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::U16(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::U24(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::U32(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::S8(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::S16(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::S24(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::S32(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::F32(buffer) => interleave(&buffer, &mut samples),
+                    AudioBufferRef::F64(buffer) => interleave(&buffer, &mut samples),
+                })
+            },
+        )?;
+
+        Ok(InterleavedAudio {
+            sample_rate,
+            channels,
+            source_format: source_format.unwrap_or((16, hound::SampleFormat::Int)),
+            samples,
+        })
+    }
+
+    /// 메모리에 있는 교차 배치 샘플을 정규화한다. option.output_format은 쓰지 않는다.
+    pub fn normalize_samples(
+        &self,
+        samples: &mut [f32],
+        sample_rate: u32,
+        channels: usize,
+        option: &AudioNormalizerOption,
+    ) -> NormalizationReport {
+        // 1. 최대 진폭, RMS 또는 라우드니스 계산
+        let mut meter = NormalizationMeter::new(sample_rate, channels, option);
+        meter.push(samples);
+        let (input_loudness, normalization_factor) = meter.measure();
+
+        // 2. 모든 샘플에 정규화 계수 적용
+        for sample in samples.iter_mut() {
+            *sample *= normalization_factor;
+        }
+
+        // 리미터로 피크를 줄인 뒤, 남은 오차는 잘라낸다.
+        let limiter_reduction_db = option
+            .limiter
+            .map(|limiter| limit(samples, channels, sample_rate, limiter))
+            .unwrap_or(0.0);
+
+        for sample in samples.iter_mut() {
+            // 클리핑 방지 (최대값을 넘지 않도록)
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+
+        NormalizationReport {
+            gain_db: 20.0 * normalization_factor.log10(),
+            limiter_reduction_db,
+            input: input_loudness,
+            output: measure(sample_rate, channels, samples),
+        }
+    }
+
+    // 오디오 정규화 함수
+    // 채널 구성과 샘플레이트는 원본을 유지하고, 모든 채널에 같은 게인을 적용한다.
+    pub fn normalize<F>(
+        &self,
+        input_path: &str,
+        output_path: &str,
+        option: AudioNormalizerOption,
+        cancel: &CancellationToken,
+        mut progress_callback: F,
+    ) -> Result<NormalizationReport, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) -> () + Send + Sync + 'static,
+    {
+        // 오디오 파일 로드
+        progress_callback(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 0.0,
+        });
+
+        let InterleavedAudio {
+            sample_rate,
+            channels,
+            source_format,
+            mut samples,
+        } = self.process_interleaved_samples(input_path, cancel, &mut progress_callback)?;
+
+        progress_callback(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 33.0,
+        });
+
+        let report = self.normalize_samples(&mut samples, sample_rate, channels, &option);
+
+        // 취소되었다면 출력 파일을 만들지 않는다.
+        if cancel.is_cancelled() {
+            return Err(AudioAnalyzerError::Cancelled);
+        }
+
+        // 3. 정규화된 오디오 파일 저장
+        progress_callback(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 90.0,
+        });
+
+        // hound 라이브러리를 사용하여 WAV 파일 저장
+        let (bits_per_sample, sample_format) = option.output_format.resolve(source_format);
+        let spec = hound::WavSpec {
+            channels: channels as u16,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        };
+        write_wav(output_path, spec, &samples)?;
+
+        progress_callback(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 100.0,
+        });
+
+        Ok(report)
+    }
+
+    // 메모리에 있는 샘플을 블록으로 나눠 SegmentStream에 넘긴다.
+    fn find_non_silent_segments<F>(
+        &self,
+        audio: &ProcessedAudio,
+        option: &AudioAnalyzerOption,
+        detector: &mut dyn SegmentDetector,
+        cancel: &CancellationToken,
+        progress_callback: &mut F,
+    ) -> Result<DetectionResult, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+    {
+        progress_callback(Progress {
+            phase: "Analyzing Audio".to_string(),
+            percentage: 0.0,
+        });
+
+        let mut stream = SegmentStream::new(audio.sample_rate, *option, detector);
+        let block_size = (audio.samples.len() / 20).max(1);
+        let mut pushed = 0;
+
+        // 윈도우 레벨 계산 진행률 업데이트 (0 ~ 40%)
+        for block in audio.samples.chunks(block_size) {
+            if cancel.is_cancelled() {
+                return Err(AudioAnalyzerError::Cancelled);
+            }
+            stream.push(block);
+            pushed += block.len();
+            progress_callback(Progress {
+                phase: "Analyzing Audio".to_string(),
+                percentage: pushed as f32 / audio.samples.len() as f32 * 40.0,
+            });
+        }
+
+        stream.finish(0.0, None, cancel, progress_callback)
+    }
+
+    pub fn start<F>(
+        &self,
+        audio_path: &str,
+        option: AudioAnalyzerOption,
+        cancel: &CancellationToken,
+        progress_callback: F,
+    ) -> Result<DetectionResult, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) -> () + Send + Sync + 'static,
+    {
+        let mut detector = option.detector.detector();
+        self.start_with_detector(
+            audio_path,
+            option,
+            detector.as_mut(),
+            cancel,
+            progress_callback,
+        )
+    }
+
+    /// option.detector 대신 직접 구현한 검출기로 분석한다.
+    /// 디코딩한 버퍼를 바로 분석에 넘기므로 파일 전체를 메모리에 올리지 않는다.
+    pub fn start_with_detector<F>(
+        &self,
+        audio_path: &str,
+        option: AudioAnalyzerOption,
+        detector: &mut dyn SegmentDetector,
+        cancel: &CancellationToken,
+        mut progress_callback: F,
+    ) -> Result<DetectionResult, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+    {
+        // 샘플레이트는 첫 버퍼를 디코딩해야 알 수 있다.
+        let mut detector = Some(detector);
+        let mut stream = None;
+        let mut samples = Vec::new();
+
+        self.decode_audio(audio_path, cancel, &mut progress_callback, |decoded, _| {
+            if let Some(detector) = detector.take() {
+                stream = Some(SegmentStream::new(decoded.spec().rate, option, detector));
+            }
+
+            samples.clear();
+            let frames = match decoded {
+                AudioBufferRef::U8(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::U16(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::U24(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::U32(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::S8(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::S16(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::S24(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::S32(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::F32(buffer) => downmix_to_mono(&buffer, &mut samples),
+                AudioBufferRef::F64(buffer) => downmix_to_mono(&buffer, &mut samples),
+            }?;
+            if let Some(stream) = stream.as_mut() {
+                stream.push(&samples);
+            }
+            Ok(frames)
+        })?;
+
+        match stream {
+            Some(stream) => stream.finish(0.0, None, cancel, &mut progress_callback),
+            None => Err(AudioAnalyzerError::UnsupportedFormat(
+                "No decodable audio samples".to_string(),
+            )),
+        }
+    }
+
+    /// 파일 대신 이미 디코딩한 모노 샘플을 분석한다.
+    pub fn start_with_samples<F>(
+        &self,
+        audio: ProcessedAudio,
+        option: AudioAnalyzerOption,
+        cancel: &CancellationToken,
+        mut progress_callback: F,
+    ) -> Result<DetectionResult, AudioAnalyzerError>
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+    {
+        let mut detector = option.detector.detector();
+        self.find_non_silent_segments(
+            &audio,
+            &option,
+            detector.as_mut(),
+            cancel,
+            &mut progress_callback,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn segment(start: f32, end: f32) -> Segment {
+        Segment::new(start, end)
+    }
+
+    fn assert_sorted_and_disjoint(segments: &[Segment], duration: f32) {
+        for segment in segments {
+            assert!(segment.start >= 0.0 && segment.end <= duration);
+            assert!(segment.start < segment.end);
+        }
+        for pair in segments.windows(2) {
+            assert!(pair[0].end < pair[1].start);
+        }
+    }
+
+    // 0.5초 단위로 소리와 무음이 번갈아 나오는 1kHz 사인파
+    fn alternating_tone(sample_rate: u32, seconds: usize) -> ProcessedAudio {
+        let samples = (0..sample_rate as usize * seconds)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                if t % 1.0 < 0.5 {
+                    0.5 * (t * 1000.0 * std::f32::consts::TAU).sin()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        ProcessedAudio {
+            sample_rate,
+            samples,
+        }
+    }
+
+    // (길이(초), RMS 레벨(dBFS)) 구간을 이어 붙인 1kHz 사인파. 레벨이 None이면 0으로 채운다.
+    fn leveled_tone(sample_rate: u32, parts: &[(f32, Option<f32>)]) -> ProcessedAudio {
+        let mut samples = Vec::new();
+        for &(seconds, level_db) in parts {
+            let amplitude = level_db.map_or(0.0, |db| db_to_amplitude(db) * 2.0_f32.sqrt());
+            for i in 0..(seconds * sample_rate as f32) as usize {
+                let t = i as f32 / sample_rate as f32;
+                samples.push(amplitude * (t * 1000.0 * std::f32::consts::TAU).sin());
+            }
+        }
+        ProcessedAudio {
+            sample_rate,
+            samples,
+        }
+    }
+
+    fn detect_segments(audio: &ProcessedAudio, option: &AudioAnalyzerOption) -> DetectionResult {
+        AudioAnalyzer::new()
+            .find_non_silent_segments(
+                audio,
+                option,
+                &mut RmsDetector,
+                &CancellationToken::new(),
+                &mut |_| {},
+            )
+            .unwrap()
+    }
+
+    fn detect_zero_buffer(audio: &ProcessedAudio, option: AudioAnalyzerOption) -> DetectionResult {
+        let option = AudioAnalyzerOption {
+            left_buffer_sec: 0.0,
+            right_buffer_sec: 0.0,
+            ..option
+        };
+        detect_segments(audio, &option)
+    }
+
+    fn assert_segments(result: &DetectionResult, expected: &[(f32, f32)]) {
+        let actual: Vec<(f32, f32)> = result.segments.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for (&(start, end), &(expected_start, expected_end)) in actual.iter().zip(expected) {
+            assert!(
+                (start - expected_start).abs() < 0.015 && (end - expected_end).abs() < 0.015,
+                "{:?}",
+                actual
+            );
+        }
+    }
+
+    fn adaptive(tracking_sec: Option<f32>) -> AudioAnalyzerOption {
+        AudioAnalyzerOption {
+            threshold_mode: ThresholdMode::Adaptive {
+                percentile: 10.0,
+                margin_db: 6.0,
+                tracking_sec,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_segments_merges_overlapping_padded_segments() {
+        let merged = merge_segments(
+            vec![segment(1.0, 2.2), segment(0.0, 0.5), segment(2.0, 3.0)],
+            0.0,
+            10.0,
+        );
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].start, merged[0].end), (0.0, 0.5));
+        assert_eq!((merged[1].start, merged[1].end), (1.0, 3.0));
+        assert_sorted_and_disjoint(&merged, 10.0);
+    }
+
+    #[test]
+    fn test_merge_segments_uses_merge_gap() {
+        let segments = vec![segment(0.0, 1.0), segment(1.2, 2.0), segment(3.0, 4.0)];
+
+        assert_eq!(merge_segments(segments.clone(), 0.0, 10.0).len(), 3);
+        assert_eq!(merge_segments(segments.clone(), 0.25, 10.0).len(), 2);
+        assert_eq!(merge_segments(segments, 1.0, 10.0).len(), 1);
+    }
+
+    #[test]
+    fn test_merge_segments_clamps_to_duration() {
+        let merged = merge_segments(
+            vec![segment(-0.1, 0.5), segment(4.5, 5.5), segment(6.0, 7.0)],
+            0.0,
+            5.0,
+        );
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].start, 0.0);
+        assert_eq!(merged[1].end, 5.0);
+        assert_sorted_and_disjoint(&merged, 5.0);
+    }
+
+    #[test]
+    fn test_find_non_silent_segments_output_is_sorted_disjoint_and_clamped() {
+        let audio = alternating_tone(16000, 4);
+        let duration = audio.samples.len() as f32 / audio.sample_rate as f32;
+
+        for (left_buffer_sec, right_buffer_sec) in [(0.0, 0.0), (0.2, 0.2), (0.3, 0.4)] {
+            let option = AudioAnalyzerOption {
+                left_buffer_sec,
+                right_buffer_sec,
+                ..Default::default()
+            };
+            let result = detect_segments(&audio, &option);
+
+            assert!(!result.segments.is_empty());
+            assert_sorted_and_disjoint(&result.segments, duration);
+        }
+    }
+
+    #[test]
+    fn test_timeline_covers_whole_file() {
+        let audio = alternating_tone(16000, 4);
+        let option = AudioAnalyzerOption {
+            left_buffer_sec: 0.1,
+            right_buffer_sec: 0.1,
+            ..Default::default()
+        };
+        let result = detect_segments(&audio, &option);
+
+        let timeline = &result.timeline;
+        assert_eq!(timeline.first().unwrap().start, 0.0);
+        assert_eq!(timeline.last().unwrap().end, result.duration);
+        for pair in timeline.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+            assert_ne!(pair[0].kind, pair[1].kind);
+        }
+
+        let kept: f32 = timeline
+            .iter()
+            .filter(|region| region.kind != RegionKind::Silence)
+            .map(|region| region.duration)
+            .sum();
+        let segments: f32 = result.segments.iter().map(|s| s.end - s.start).sum();
+        assert!((kept - segments).abs() < 1e-4);
+        assert!(timeline
+            .iter()
+            .any(|region| region.kind == RegionKind::Margin));
+    }
+
+    #[test]
+    fn test_segment_stats() {
+        let audio = alternating_tone(16000, 2);
+        let option = AudioAnalyzerOption::default();
+        let result = detect_zero_buffer(&audio, option);
+
+        let stats = result.segments[0].stats.unwrap();
+        // 진폭 0.5 사인파: 피크 약 -6 dBFS, RMS 약 -9 dBFS
+        assert!((stats.peak_db + 6.02).abs() < 0.1);
+        assert!((stats.rms_db + 9.03).abs() < 0.1);
+        assert!((stats.mean_relative_db - (-9.03 - option.threshold_db)).abs() < 0.5);
+        assert_eq!(stats.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_find_non_silent_segments_stops_when_cancelled() {
+        let analyzer = AudioAnalyzer::new();
+        let audio = alternating_tone(16000, 2);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let result = analyzer.find_non_silent_segments(
+            &audio,
+            &AudioAnalyzerOption::default(),
+            &mut RmsDetector,
+            &cancel,
+            &mut |_| {},
+        );
+        assert!(matches!(result, Err(AudioAnalyzerError::Cancelled)));
+    }
+
+    #[test]
+    fn test_normalize_preserves_channels_and_bit_depth() {
+        let dir = std::env::temp_dir();
+        let input_path = dir.join(format!(
+            "test_normalize_preserves_channels_and_bit_depth_{}_in.wav",
+            std::process::id()
+        ));
+        let output_path = dir.join(format!(
+            "test_normalize_preserves_channels_and_bit_depth_{}_out.wav",
+            std::process::id()
+        ));
+
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 24,
+            sample_format: hound::SampleFormat::Int,
+        };
+        // 왼쪽은 진폭 0.25, 오른쪽은 0.125인 사인파
+        let mut samples = Vec::new();
+        for i in 0..48000 {
+            let value = (i as f32 / 48000.0 * 440.0 * std::f32::consts::TAU).sin();
+            samples.extend([value * 0.25, value * 0.125]);
+        }
+        write_wav(input_path.to_str().unwrap(), spec, &samples).unwrap();
+
+        let analyzer = AudioAnalyzer::new();
+        analyzer
+            .normalize(
+                input_path.to_str().unwrap(),
+                output_path.to_str().unwrap(),
+                AudioNormalizerOption::default(),
+                &CancellationToken::new(),
+                |_| {},
+            )
+            .unwrap();
+
+        let mut reader = hound::WavReader::open(&output_path).unwrap();
+        assert_eq!(reader.spec(), spec);
+
+        // 두 채널에 같은 게인이 적용되어 채널 간 비율이 유지되어야 한다.
+        let max = (1 << 23) as f32;
+        let (mut left, mut right) = (0.0_f32, 0.0_f32);
+        let output: Vec<i32> = reader.samples::<i32>().map(|s| s.unwrap()).collect();
+        for frame in output.chunks_exact(2) {
+            left = left.max(frame[0].abs() as f32 / max);
+            right = right.max(frame[1].abs() as f32 / max);
+        }
+        assert!((amplitude_to_db(left) + 3.0).abs() < 0.1);
+        assert!((left / right - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_adaptive_threshold_follows_noise_floor() {
+        let audio = leveled_tone(
+            16000,
+            &[(2.0, Some(-47.0)), (1.0, Some(-12.0)), (2.0, Some(-47.0))],
+        );
+        let result = detect_zero_buffer(&audio, adaptive(None));
+
+        assert!((result.noise_floor_db.unwrap() + 47.0).abs() < 0.5);
+        assert!((result.threshold_db + 41.0).abs() < 0.5);
+        assert_segments(&result, &[(2.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_adaptive_threshold_ignores_digital_silence() {
+        let audio = leveled_tone(
+            16000,
+            &[
+                (2.0, None),
+                (3.0, Some(-47.0)),
+                (1.0, Some(-12.0)),
+                (3.0, Some(-47.0)),
+            ],
+        );
+        let result = detect_zero_buffer(&audio, adaptive(None));
+
+        assert!((result.noise_floor_db.unwrap() + 47.0).abs() < 0.5);
+        assert_segments(&result, &[(5.0, 6.0)]);
+
+        // 전부 디지털 무음이면 threshold_db를 쓴다.
+        let silence = leveled_tone(16000, &[(1.0, None)]);
+        let result = detect_zero_buffer(&silence, adaptive(None));
+        assert_eq!(result.noise_floor_db, None);
+        assert_eq!(
+            result.threshold_db,
+            AudioAnalyzerOption::default().threshold_db
+        );
+        assert!(result.segments.is_empty());
+    }
+
+    #[test]
+    fn test_adaptive_threshold_tracks_changing_noise_floor() {
+        // 앞쪽 0 패딩 뒤에 잡음이 -60 dBFS에서 -40 dBFS로 올라간다.
+        let audio = leveled_tone(
+            16000,
+            &[
+                (2.0, None),
+                (3.0, Some(-60.0)),
+                (1.0, Some(-20.0)),
+                (3.0, Some(-60.0)),
+                (3.0, Some(-40.0)),
+                (1.0, Some(-20.0)),
+                (3.0, Some(-40.0)),
+            ],
+        );
+
+        // 파일 전체의 노이즈 플로어로는 -40 dBFS 잡음도 소리로 판정된다.
+        let result = detect_zero_buffer(&audio, adaptive(None));
+        assert_segments(&result, &[(5.0, 6.0), (9.0, 16.0)]);
+
+        // 잡음이 바뀌는 경계에서는 플로어가 보간되며 따라가므로 그 뒤의 잡음만 확인한다.
+        let result = detect_zero_buffer(&audio, adaptive(Some(2.0)));
+        let covers = |start: f32, end: f32| {
+            result
+                .segments
+                .iter()
+                .any(|s| s.start <= start + 0.015 && s.end >= end - 0.015)
+        };
+        assert!(
+            covers(5.0, 6.0) && covers(12.0, 13.0),
+            "{:?}",
+            result.segments
+        );
+        assert!(
+            result.segments.iter().all(|s| s.end < 13.015),
+            "{:?}",
+            result.segments
+        );
+        assert!(
+            result.segments.iter().all(|s| s.start > 4.985),
+            "{:?}",
+            result.segments
+        );
+    }
+
+    #[test]
+    fn test_long_durations_do_not_overflow() {
+        let audio = alternating_tone(48000, 3);
+        let option = AudioAnalyzerOption {
+            min_silence_ms: 120_000,
+            hold_ms: 120_000,
+            ..Default::default()
+        };
+        let result = detect_zero_buffer(&audio, option);
+        assert_eq!(result.segments.len(), 1);
+    }
+
+    #[test]
+    fn test_overlapping_windows_keep_segment_edges() {
+        let audio = leveled_tone(16000, &[(0.7, None), (0.5, Some(-12.0)), (0.8, None)]);
+        for (window_ms, hop_ms) in [(10, 10), (30, 10), (20, 5)] {
+            let option = AudioAnalyzerOption {
+                window_ms,
+                hop_ms,
+                ..Default::default()
+            };
+            let result = detect_zero_buffer(&audio, option);
+            assert_segments(&result, &[(0.7, 1.2)]);
+        }
+    }
+
+    #[test]
+    fn test_start_reads_wav_sample_formats() {
+        // 1초 무음, 진폭 0.5(피크 -6dBFS, RMS -9dBFS)의 1초 사인파, 1초 무음
+        let samples: Vec<f32> = (0..48000)
+            .map(|i| {
+                let value = (i as f32 / 16000.0 * 440.0 * std::f32::consts::TAU).sin();
+                if (16000..32000).contains(&i) {
+                    value * 0.5
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        for (name, bits_per_sample, sample_format) in [
+            ("u8", 8, hound::SampleFormat::Int),
+            ("s24", 24, hound::SampleFormat::Int),
+            ("f32", 32, hound::SampleFormat::Float),
+        ] {
+            let path = std::env::temp_dir().join(format!(
+                "test_start_reads_wav_sample_formats_{}_{}.wav",
+                std::process::id(),
+                name
+            ));
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate: 16000,
+                bits_per_sample,
+                sample_format,
+            };
+            write_wav(path.to_str().unwrap(), spec, &samples).unwrap();
+
+            let option = AudioAnalyzerOption {
+                left_buffer_sec: 0.0,
+                right_buffer_sec: 0.0,
+                ..Default::default()
+            };
+            let result = AudioAnalyzer::new()
+                .start(
+                    path.to_str().unwrap(),
+                    option,
+                    &CancellationToken::new(),
+                    |_| {},
+                )
+                .unwrap();
+
+            assert_segments(&result, &[(1.0, 2.0)]);
+            let stats = result.segments[0].stats.unwrap();
+            assert!((stats.peak_db + 6.02).abs() < 0.2, "{}: {:?}", name, stats);
+            assert!((stats.rms_db + 9.03).abs() < 0.2, "{}: {:?}", name, stats);
+        }
+    }
+
+    #[test]
+    fn test_hold_bridges_short_dip() {
+        let audio = leveled_tone(
+            16000,
+            &[
+                (0.5, None),
+                (0.5, Some(-12.0)),
+                (0.08, None),
+                (0.42, Some(-12.0)),
+                (0.5, None),
+            ],
+        );
+
+        let result = detect_zero_buffer(&audio, AudioAnalyzerOption::default());
+        assert_segments(&result, &[(0.5, 1.0), (1.08, 1.5)]);
+
+        // hold보다 짧은 끊김은 구간을 끝내지 않고, 구간 끝은 닫힘 조건이 처음 된 위치로 남는다.
+        let option = AudioAnalyzerOption {
+            hold_ms: 150,
+            ..Default::default()
+        };
+        let result = detect_zero_buffer(&audio, option);
+        assert_segments(&result, &[(0.5, 1.5)]);
+    }
+
+    #[test]
+    fn test_close_threshold_prevents_chatter() {
+        // 큰 소리 뒤에 임계값(-40dBFS) 위아래를 오가는 잔향이 이어진다.
+        let mut parts = vec![(0.5, None), (0.3, Some(-20.0))];
+        for _ in 0..8 {
+            parts.extend([(0.06, Some(-38.0)), (0.06, Some(-43.0))]);
+        }
+        parts.push((0.5, None));
+        let audio = leveled_tone(16000, &parts);
+
+        let result = detect_zero_buffer(&audio, AudioAnalyzerOption::default());
+        assert!(result.segments.len() > 5, "{:?}", result.segments);
+
+        let option = AudioAnalyzerOption {
+            close_threshold_db: Some(-50.0),
+            ..Default::default()
+        };
+        let result = detect_zero_buffer(&audio, option);
+        assert_segments(&result, &[(0.5, 1.76)]);
+    }
+
+    #[test]
+    fn test_close_threshold_above_open_threshold_is_clamped() {
+        // 열림(-40dBFS)과 닫힘(-20dBFS) 임계값 사이의 소리
+        let audio = leveled_tone(16000, &[(0.5, None), (1.0, Some(-30.0)), (0.5, None)]);
+        let option = AudioAnalyzerOption {
+            close_threshold_db: Some(-20.0),
+            ..Default::default()
+        };
+
+        let result = detect_zero_buffer(&audio, option);
+        assert_segments(&result, &[(0.5, 1.5)]);
+    }
+
+    #[test]
+    fn test_source_sample_format_prefers_codec_bit_depth() {
+        use std::borrow::Cow;
+        use symphonia::core::audio::{Channels, SignalSpec};
+
+        let spec = SignalSpec::new(44100, Channels::FRONT_LEFT);
+        let int_buffer = AudioBuffer::<i32>::new(0, spec);
+        let float_buffer = AudioBuffer::<f32>::new(0, spec);
+        let s32 = AudioBufferRef::S32(Cow::Borrowed(&int_buffer));
+        let f32 = AudioBufferRef::F32(Cow::Borrowed(&float_buffer));
+
+        // 24비트 FLAC은 S32로 디코딩된다.
+        assert_eq!(
+            source_sample_format(&s32, Some(24)),
+            (24, hound::SampleFormat::Int)
+        );
+        assert_eq!(
+            source_sample_format(&s32, None),
+            (32, hound::SampleFormat::Int)
+        );
+        // 실수 PCM은 실수로, MP3 같은 손실 압축 원본은 16비트 정수로 저장한다.
+        assert_eq!(
+            source_sample_format(&f32, Some(32)),
+            (32, hound::SampleFormat::Float)
+        );
+        assert_eq!(
+            source_sample_format(&f32, None),
+            (16, hound::SampleFormat::Int)
+        );
+    }
+}
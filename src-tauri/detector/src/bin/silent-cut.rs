@@ -0,0 +1,260 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+#[cfg(feature = "deepfilter")]
+use silent_cut_detector::deepfilter::Preset;
+use silent_cut_detector::{
+    analyzer::{DetectorKind, NormalizationMode, Progress, ThresholdMode},
+    cancel::CancellationToken,
+    fcpxml::{generate_fcpxml, Setting},
+    pipeline::{run_analysis, AnalysisSetting},
+    vad::VadOption,
+};
+
+/// GUI 없이 비디오의 소리 구간을 분석한다.
+#[derive(Parser)]
+#[command(name = "silent-cut", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 비디오를 분석해 소리 구간을 JSON으로 출력한다.
+    Analyze(AnalyzeArgs),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ModeArg {
+    Peak,
+    Rms,
+    Loudness,
+}
+
+#[cfg(feature = "deepfilter")]
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum PresetArg {
+    Gentle,
+    #[default]
+    Default,
+    Aggressive,
+}
+
+#[derive(Args)]
+struct AnalyzeArgs {
+    /// 분석할 비디오 파일
+    input: String,
+
+    /// 소리 구간이 시작되는 임계값 (dBFS)
+    #[arg(long, allow_hyphen_values = true)]
+    threshold: Option<f32>,
+    /// 소리 구간이 끝나는 임계값 (dBFS)
+    #[arg(long, allow_hyphen_values = true)]
+    close_threshold: Option<f32>,
+    /// 노이즈 플로어보다 이만큼(dB) 위에 임계값을 둔다. 지정하면 --threshold는 무시된다.
+    #[arg(long)]
+    adaptive: Option<f32>,
+    /// 노이즈 플로어로 쓸 RMS 하위 백분위
+    #[arg(long, default_value_t = 10.0, requires = "adaptive")]
+    percentile: f32,
+    /// 노이즈 플로어를 다시 추정하는 구간 길이 (초)
+    #[arg(long, requires = "adaptive")]
+    tracking: Option<f32>,
+    /// 음성 특징을 가진 구간만 소리로 판정한다.
+    #[arg(long)]
+    vad: bool,
+
+    /// 최소 소리 구간 길이 (ms)
+    #[arg(long)]
+    min_duration: Option<u32>,
+    /// 이보다 짧은 무음은 자르지 않는다 (ms)
+    #[arg(long)]
+    min_silence: Option<u32>,
+    #[arg(long)]
+    attack: Option<u32>,
+    #[arg(long)]
+    hold: Option<u32>,
+    /// 소리 구간 앞에 더하는 여유 (초)
+    #[arg(long)]
+    left_buffer: Option<f32>,
+    /// 소리 구간 뒤에 더하는 여유 (초)
+    #[arg(long)]
+    right_buffer: Option<f32>,
+    /// 이 간격(초) 이하로 떨어진 구간은 하나로 합친다.
+    #[arg(long)]
+    merge_gap: Option<f32>,
+    /// 분석 윈도우 길이 (ms)
+    #[arg(long)]
+    window: Option<u32>,
+    /// 분석 윈도우 간격 (ms)
+    #[arg(long)]
+    hop: Option<u32>,
+
+    /// 분석 전에 이 레벨로 정규화한다 (dBFS 또는 LUFS).
+    #[arg(long, allow_hyphen_values = true)]
+    normalize: Option<f32>,
+    #[arg(long, value_enum, default_value = "peak")]
+    normalization_mode: ModeArg,
+
+    /// DeepFilterNet 모델 파일. 지정하면 분석 전에 잡음을 제거한다.
+    #[cfg(feature = "deepfilter")]
+    #[arg(long)]
+    deepfilter_model: Option<String>,
+    #[cfg(feature = "deepfilter")]
+    #[arg(long, value_enum, default_value = "default")]
+    deepfilter_preset: PresetArg,
+
+    /// 분석 결과로 만든 FCPXML을 저장할 경로
+    #[arg(long)]
+    fcpxml: Option<String>,
+    /// FCPXML의 FPS (29.97, 30, 59.94, 60)
+    #[arg(long, default_value = "30")]
+    fps: String,
+    /// FCPXML의 해상도 (SD, HD, FHD, 4K)
+    #[arg(long, default_value = "FHD")]
+    resolution: String,
+
+    /// JSON을 저장할 경로. 없으면 표준 출력에 쓴다.
+    #[arg(long, short)]
+    output: Option<String>,
+    /// 추출한 오디오와 분석용 오디오를 임시 폴더에 WAV로 남긴다.
+    #[arg(long)]
+    keep_audio: bool,
+    /// 진행률을 표시하지 않는다.
+    #[arg(long, short)]
+    quiet: bool,
+}
+
+impl AnalyzeArgs {
+    fn setting(&self) -> AnalysisSetting {
+        let mut setting = AnalysisSetting {
+            export_raw: self.keep_audio,
+            export_analysis: self.keep_audio,
+            ..Default::default()
+        };
+
+        if let Some(target_db) = self.normalize {
+            setting.use_normalize = true;
+            setting.target_db = target_db;
+            setting.normalization_mode = match self.normalization_mode {
+                ModeArg::Peak => NormalizationMode::Peak,
+                ModeArg::Rms => NormalizationMode::Rms,
+                ModeArg::Loudness => NormalizationMode::Loudness,
+            };
+        }
+
+        #[cfg(feature = "deepfilter")]
+        {
+            setting.use_deepfilternet = self.deepfilter_model.is_some();
+            setting.export_denoised = self.keep_audio;
+            setting.deepfilter_preset = match self.deepfilter_preset {
+                PresetArg::Gentle => Preset::Gentle,
+                PresetArg::Default => Preset::Default,
+                PresetArg::Aggressive => Preset::Aggressive,
+            };
+        }
+
+        // 지정하지 않은 값은 분석기 기본값을 쓴다.
+        let option = &mut setting.analyzer;
+        if self.vad {
+            option.detector = DetectorKind::Vad(VadOption::default());
+        }
+        if let Some(margin_db) = self.adaptive {
+            option.threshold_mode = ThresholdMode::Adaptive {
+                percentile: self.percentile,
+                margin_db,
+                tracking_sec: self.tracking,
+            };
+        }
+        option.threshold_db = self.threshold.unwrap_or(option.threshold_db);
+        option.close_threshold_db = self.close_threshold;
+        option.attack_ms = self.attack.unwrap_or(option.attack_ms);
+        option.hold_ms = self.hold.unwrap_or(option.hold_ms);
+        option.min_duration_ms = self.min_duration.unwrap_or(option.min_duration_ms);
+        option.min_silence_ms = self.min_silence.unwrap_or(option.min_silence_ms);
+        option.left_buffer_sec = self.left_buffer.unwrap_or(option.left_buffer_sec);
+        option.right_buffer_sec = self.right_buffer.unwrap_or(option.right_buffer_sec);
+        option.merge_gap_sec = self.merge_gap.unwrap_or(option.merge_gap_sec);
+        option.window_ms = self.window.unwrap_or(option.window_ms);
+        option.hop_ms = self.hop.unwrap_or(option.hop_ms);
+
+        setting
+    }
+}
+
+fn analyze(args: AnalyzeArgs) -> Result<(), String> {
+    // 분석이 끝난 뒤에 실패하지 않도록 FCPXML 설정을 먼저 확인한다.
+    let fcpxml_setting = match &args.fcpxml {
+        Some(_) => Some(Setting::parse(&args.fps, &args.resolution).map_err(|e| e.to_string())?),
+        None => None,
+    };
+
+    // 오디오는 메모리에서만 처리하므로 --keep-audio일 때만 폴더를 만든다.
+    let work_dir = std::env::temp_dir().join(format!("silent-cut-{}", std::process::id()));
+    if args.keep_audio {
+        std::fs::create_dir_all(&work_dir).map_err(|e| e.to_string())?;
+    }
+
+    let quiet = args.quiet;
+    let emit_progress = move |progress: Progress| {
+        if !quiet {
+            eprint!("\r{:<28} {:>5.1}%", progress.phase, progress.percentage);
+        }
+    };
+    #[cfg(feature = "deepfilter")]
+    let model_path = args.deepfilter_model.as_deref().unwrap_or_default();
+    #[cfg(not(feature = "deepfilter"))]
+    let model_path = "";
+    let result = run_analysis(
+        &args.input,
+        args.setting(),
+        model_path,
+        &work_dir,
+        &CancellationToken::new(),
+        emit_progress,
+    );
+    if !quiet {
+        eprintln!();
+    }
+    let result = result.map_err(|e| e.to_string())?;
+    if args.keep_audio && !quiet {
+        eprintln!("Audio: {}", work_dir.display());
+    }
+
+    if let (Some(path), Some(setting)) = (&args.fcpxml, fcpxml_setting) {
+        generate_fcpxml(setting, &args.input, result.segments.clone(), None, path)
+            .map_err(|e| e.to_string())?;
+        if !quiet {
+            eprintln!("FCPXML: {}", path);
+        }
+    }
+
+    let writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path).map_err(|e| e.to_string())?),
+        None => Box::new(std::io::stdout()),
+    };
+    let mut writer = BufWriter::new(writer);
+    serde_json::to_writer_pretty(&mut writer, &result).map_err(|e| e.to_string())?;
+    writeln!(writer).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Analyze(args) => analyze(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+// 무음(0)에서 -inf가 되지 않도록 하한을 둔다.
+const MIN_LEVEL_DB: f32 = -100.0;
+
+/// 분석 전에 소리 크기 차이를 줄이는 컴프레서 설정
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CompressorOption {
+    /// 압축이 시작되는 레벨 (dBFS)
+    pub threshold_db: f32,
+    /// threshold_db를 넘는 부분의 압축 비율 (예: 4.0이면 4:1)
+    pub ratio: f32,
+    /// 게인을 줄이는 데 걸리는 시간 (ms)
+    pub attack_ms: f32,
+    /// 줄어든 게인이 돌아오는 데 걸리는 시간 (ms)
+    pub release_ms: f32,
+    /// 압축 후 전체에 더하는 게인 (dB)
+    pub makeup_db: f32,
+}
+
+impl Default for CompressorOption {
+    fn default() -> Self {
+        Self {
+            threshold_db: -30.0,
+            ratio: 4.0,
+            attack_ms: 10.0,
+            release_ms: 200.0,
+            makeup_db: 0.0,
+        }
+    }
+}
+
+// 시간 상수에 해당하는 1차 평활 계수. rate는 초당 갱신 횟수
+fn smoothing_coefficient(rate: f32, time_ms: f32) -> f32 {
+    if time_ms > 0.0 {
+        (-1000.0 / (rate * time_ms)).exp()
+    } else {
+        0.0
+    }
+}
+
+// 레벨(dBFS)을 차례로 받아 attack/release로 평활한 게인(dB)을 구한다.
+struct GainComputer {
+    option: CompressorOption,
+    slope: f32,
+    attack: f32,
+    release: f32,
+    // 게인 감소량 (dB, 0 이상)
+    reduction_db: f32,
+}
+
+impl GainComputer {
+    fn new(rate: f32, option: CompressorOption) -> Self {
+        Self {
+            option,
+            slope: 1.0 - 1.0 / option.ratio.max(1.0),
+            attack: smoothing_coefficient(rate, option.attack_ms),
+            release: smoothing_coefficient(rate, option.release_ms),
+            reduction_db: 0.0,
+        }
+    }
+
+    fn gain_db(&mut self, level_db: f32) -> f32 {
+        let target_db = (level_db - self.option.threshold_db).max(0.0) * self.slope;
+
+        let coefficient = if target_db > self.reduction_db {
+            self.attack
+        } else {
+            self.release
+        };
+        self.reduction_db = target_db + (self.reduction_db - target_db) * coefficient;
+
+        self.option.makeup_db - self.reduction_db
+    }
+}
+
+/// 피드포워드 방식으로 모노 샘플을 압축한다.
+pub fn compress(samples: &mut [f32], sample_rate: u32, option: CompressorOption) {
+    let mut computer = GainComputer::new(sample_rate as f32, option);
+    for sample in samples.iter_mut() {
+        let level_db = (20.0 * sample.abs().log10()).max(MIN_LEVEL_DB);
+        *sample *= 10.0_f32.powf(computer.gain_db(level_db) / 20.0);
+    }
+}
+
+/// 윈도우별 최대 절대값으로 윈도우마다 적용할 게인(진폭 배율)을 구한다.
+/// 샘플 대신 윈도우 간격으로 평활하므로, 샘플 단위로 압축한 뒤 잰 윈도우 레벨과 근사적으로 같다.
+pub fn window_gains(peaks: &[f32], windows_per_sec: f32, option: CompressorOption) -> Vec<f32> {
+    let mut computer = GainComputer::new(windows_per_sec, option);
+    peaks
+        .iter()
+        .map(|&peak| {
+            let level_db = (20.0 * peak.log10()).max(MIN_LEVEL_DB);
+            10.0_f32.powf(computer.gain_db(level_db) / 20.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rms_db(samples: &[f32]) -> f32 {
+        let mean_square = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+        10.0 * mean_square.log10()
+    }
+
+    #[test]
+    fn test_compress_reduces_level_difference() {
+        let sample_rate = 16000;
+        // 앞 1초는 진폭 0.5, 뒤 2초는 진폭 0.02인 사인파 (약 28dB 차이)
+        let mut samples: Vec<f32> = (0..sample_rate * 3)
+            .map(|i| {
+                let amplitude = if i < sample_rate { 0.5 } else { 0.02 };
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (t * 440.0 * std::f32::consts::TAU).sin()
+            })
+            .collect();
+
+        compress(
+            &mut samples,
+            sample_rate as u32,
+            CompressorOption::default(),
+        );
+
+        // release가 끝난 뒤의 작은 소리는 threshold 아래라 그대로 남는다.
+        let loud = rms_db(&samples[sample_rate / 2..sample_rate]);
+        let quiet = rms_db(&samples[sample_rate * 2..]);
+        assert!((quiet - (20.0 * 0.02_f32.log10() - 3.01)).abs() < 0.1);
+        assert!(loud - quiet < 15.0);
+    }
+
+    #[test]
+    fn test_window_gains_match_sample_compression() {
+        let sample_rate = 16000;
+        let hop = sample_rate / 100;
+        let samples: Vec<f32> = (0..sample_rate * 3)
+            .map(|i| {
+                let amplitude = if i < sample_rate { 0.5 } else { 0.02 };
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (t * 440.0 * std::f32::consts::TAU).sin()
+            })
+            .collect();
+        let mut compressed = samples.clone();
+        compress(
+            &mut compressed,
+            sample_rate as u32,
+            CompressorOption::default(),
+        );
+
+        let peaks: Vec<f32> = samples
+            .chunks(hop)
+            .map(|chunk| chunk.iter().fold(0.0_f32, |peak, &s| peak.max(s.abs())))
+            .collect();
+        let gains = window_gains(&peaks, 100.0, CompressorOption::default());
+
+        // attack이 끝난 큰 소리와 release가 끝난 작은 소리에서 비교한다.
+        for range in [20..100, 200..300] {
+            for i in range {
+                let window = i * hop..(i + 1) * hop;
+                let expected = rms_db(&compressed[window.clone()]);
+                let actual = rms_db(&samples[window]) + 20.0 * gains[i].log10();
+                assert!(
+                    (expected - actual).abs() < 1.0,
+                    "{}: {} {}",
+                    i,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fmt::Display,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+use ez_ffmpeg::{
+    stream_info::{find_audio_stream_info, find_video_stream_info, StreamInfo},
+    FfmpegContext, FfmpegScheduler,
+};
+use serde::{Deserialize, Serialize};
+
+use super::cancel::CancellationToken;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConverterError {
+    #[error("Failed to convert video to audio: {0}")]
+    ConversionError(String),
+    #[error("No video stream found")]
+    NoVideoStreamFound,
+    #[error("Failed to get video info: {0}")]
+    GetVideoInfoError(String),
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+/// [`stream_video_audio`]가 내보내는 오디오의 샘플레이트. 채널은 모노로 다운믹스한다.
+pub const STREAM_SAMPLE_RATE: u32 = 48000;
+
+// 받는 쪽이 밀렸을 때 ffmpeg가 앞서 디코딩해 둘 수 있는 출력 블록 수
+const STREAM_QUEUE_BLOCKS: usize = 64;
+// 취소와 변환 종료를 확인하는 간격
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoInfo {
+    pub duration: i64,
+    pub width: i32,
+    pub height: i32,
+    pub fps: f64,
+}
+
+pub fn get_video_info(video_path: &str) -> Result<VideoInfo, ConverterError> {
+    let context = find_video_stream_info(video_path)
+        .map_err(|e| ConverterError::GetVideoInfoError(e.to_string()))?;
+
+    if let Some(stream_info) = context {
+        if let StreamInfo::Video {
+            duration,
+            width,
+            height,
+            fps,
+            ..
+        } = stream_info
+        {
+            return Ok(VideoInfo {
+                duration,
+                width,
+                height,
+                fps,
+            });
+        }
+    }
+
+    Err(ConverterError::NoVideoStreamFound)
+}
+
+pub fn convert_video_to_audio(
+    video_path: &str,
+    audio_path: &str,
+    cancel: &CancellationToken,
+) -> Result<(), ConverterError> {
+    let context = FfmpegContext::builder()
+        .input(video_path)
+        .output(
+            ez_ffmpeg::Output::new(audio_path)
+                .set_audio_codec("pcm_s16le")
+                .set_audio_sample_rate(48000)
+                .set_audio_channels(1),
+        )
+        .build()
+        .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+
+    let scheduler = FfmpegScheduler::new(context)
+        .start()
+        .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+
+    // 변환이 끝날 때까지 취소 여부를 확인한다. 취소되면 만들다 만 파일은 지운다.
+    while !scheduler.is_ended() {
+        if cancel.is_cancelled() {
+            scheduler.abort();
+            let _ = std::fs::remove_file(audio_path);
+            return Err(ConverterError::Cancelled);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    scheduler
+        .wait()
+        .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// 오디오 스트림 길이로 어림한 [`STREAM_SAMPLE_RATE`] 기준 프레임 수. 알 수 없으면 None
+pub fn estimate_stream_frames(video_path: &str) -> Option<usize> {
+    match find_audio_stream_info(video_path).ok()?? {
+        StreamInfo::Audio {
+            duration,
+            time_base,
+            ..
+        } if duration > 0 && time_base.den > 0 => {
+            let seconds = duration as f64 * time_base.num as f64 / time_base.den as f64;
+            Some((seconds * STREAM_SAMPLE_RATE as f64) as usize)
+        }
+        _ => None,
+    }
+}
+
+/// ffmpeg가 디코딩한 오디오를 [-1, 1] 범위의 모노 f32 블록으로 차례로 돌려준다.
+/// 취소되면 [`ConverterError::Cancelled`]를 돌려준다.
+pub struct AudioBlocks<'a> {
+    receiver: Receiver<Vec<u8>>,
+    is_ended: &'a dyn Fn() -> bool,
+    cancel: &'a CancellationToken,
+    // 블록 경계에서 잘린 16비트 샘플의 앞 바이트
+    carry: Option<u8>,
+}
+
+impl AudioBlocks<'_> {
+    fn convert(&mut self, bytes: Vec<u8>) -> Vec<f32> {
+        let mut data = Vec::with_capacity(bytes.len() + 1);
+        data.extend(self.carry.take());
+        data.extend_from_slice(&bytes);
+        if data.len() % 2 == 1 {
+            self.carry = data.pop();
+        }
+        data.chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+            .collect()
+    }
+}
+
+impl Iterator for AudioBlocks<'_> {
+    type Item = Result<Vec<f32>, ConverterError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.cancel.is_cancelled() {
+                return Some(Err(ConverterError::Cancelled));
+            }
+            match self.receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(bytes) => return Some(Ok(self.convert(bytes))),
+                // 변환이 끝났으면 남아 있는 블록만 마저 꺼낸다.
+                Err(RecvTimeoutError::Timeout) if (self.is_ended)() => {
+                    let bytes = self.receiver.try_recv().ok()?;
+                    return Some(Ok(self.convert(bytes)));
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+/// 파일을 만들지 않고 비디오의 오디오를 48kHz 모노로 디코딩해 consume에 블록 단위로 넘긴다.
+/// consume이 오류를 돌려주면 디코딩을 중단한다.
+pub fn stream_video_audio<T, E, F>(
+    video_path: &str,
+    cancel: &CancellationToken,
+    consume: F,
+) -> Result<T, E>
+where
+    E: From<ConverterError>,
+    F: FnOnce(AudioBlocks) -> Result<T, E>,
+{
+    let (sender, receiver) = mpsc::sync_channel(STREAM_QUEUE_BLOCKS);
+    let context = FfmpegContext::builder()
+        .input(video_path)
+        .output(
+            ez_ffmpeg::Output::new_by_write_callback(move |buf: &[u8]| {
+                // 받는 쪽이 먼저 끝났으면 실패를 돌려 ffmpeg를 멈춘다.
+                match sender.send(buf.to_vec()) {
+                    Ok(()) => buf.len() as i32,
+                    Err(_) => -1,
+                }
+            })
+            .set_format("s16le")
+            .set_audio_codec("pcm_s16le")
+            .set_audio_sample_rate(48000)
+            .set_audio_channels(1),
+        )
+        .build()
+        .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+
+    let scheduler = FfmpegScheduler::new(context)
+        .start()
+        .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+
+    let is_ended = || scheduler.is_ended();
+    let result = consume(AudioBlocks {
+        receiver,
+        is_ended: &is_ended,
+        cancel,
+        carry: None,
+    });
+
+    // consume이 끝나면 receiver도 정리되므로 ffmpeg가 쓰기에서 멈춰 있지 않는다.
+    match result {
+        Ok(value) => {
+            scheduler
+                .wait()
+                .map_err(|e| ConverterError::ConversionError(e.to_string()))?;
+            Ok(value)
+        }
+        Err(e) => {
+            scheduler.abort();
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convert_video_to_audio() {
+        let result = convert_video_to_audio(
+            "/Users/jkpark/Parks/99-Record/2025-05-04 17-44-19.mp4",
+            "/Users/jkpark/Parks/01-/silent-cut-for-fcp/test22.wav",
+            &CancellationToken::new(),
+        );
+
+        assert_eq!(result.is_ok(), true);
+    }
+}
@@ -0,0 +1,953 @@
+use std::{fs::File, io::BufReader};
+
+use super::analyzer::{create_wav, finalize_wav, write_samples};
+use super::cancel::CancellationToken;
+use super::resampler::Resampler;
+use df::tract::{DfParams, DfTract, RuntimeParams};
+use hound::WavReader;
+use ndarray::{Array2, ArrayView1};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeepFilterNetError {
+    #[error("Input not valid: {0}")]
+    InputNotValid(String),
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+/// DeepFilterNet 런타임 파라미터
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Parameter {
+    /// 잡음으로 판단된 구간을 한 번 더 줄이는 후처리 필터
+    pub post_filter: bool,
+    pub post_filter_beta: f32,
+    /// STFT와 모델 lookahead로 생기는 지연을 보정한다.
+    pub compensate_delay: bool,
+    /// 잡음 감쇠량 상한 (dB). 작을수록 원음이 더 많이 남는다.
+    pub atten_lim_db: f32,
+    /// 국소 SNR이 이 값보다 낮으면 잡음만 있는 구간으로 보고 모두 줄인다. (dB)
+    pub min_db_thresh: f32,
+    /// 국소 SNR이 이 값보다 높으면 ERB 단계를 건너뛴다. (dB)
+    pub max_db_erb_thresh: f32,
+    /// 국소 SNR이 이 값보다 높으면 DF 단계를 건너뛴다. (dB)
+    pub max_db_df_thresh: f32,
+    /// 여러 채널의 마스크를 합치는 방법 (0: 없음, 1: 최대, 2: 평균)
+    pub reduce_mask: i32,
+    /// 긴 오디오를 청크로 나눠 동시에 처리할 워커 수. 0이면 CPU 코어 수만큼 사용한다.
+    pub workers: usize,
+}
+
+impl Default for Parameter {
+    fn default() -> Self {
+        Self {
+            post_filter: true,
+            post_filter_beta: 0.02,
+            compensate_delay: true,
+            atten_lim_db: 100.0,
+            min_db_thresh: -15.0,
+            max_db_erb_thresh: 35.0,
+            max_db_df_thresh: 35.0,
+            reduce_mask: 1,
+            workers: 0,
+        }
+    }
+}
+
+impl Parameter {
+    pub fn validate(&self) -> Result<(), DeepFilterNetError> {
+        let invalid =
+            |message: &str| Err(DeepFilterNetError::InvalidParameter(message.to_string()));
+
+        if !(self.post_filter_beta.is_finite() && self.post_filter_beta >= 0.0) {
+            return invalid("post_filter_beta must be a non-negative number");
+        }
+        if !(self.atten_lim_db.is_finite() && self.atten_lim_db >= 0.0) {
+            return invalid("atten_lim_db must be a non-negative number");
+        }
+        if !(self.min_db_thresh < self.max_db_erb_thresh
+            && self.min_db_thresh < self.max_db_df_thresh)
+        {
+            return invalid(
+                "min_db_thresh must be lower than max_db_erb_thresh and max_db_df_thresh",
+            );
+        }
+        if !(0..=2).contains(&self.reduce_mask) {
+            return invalid("reduce_mask must be 0, 1 or 2");
+        }
+        Ok(())
+    }
+
+    pub fn worker_count(&self) -> usize {
+        if self.workers == 0 {
+            std::thread::available_parallelism()
+                .map(|count| count.get())
+                .unwrap_or(1)
+        } else {
+            self.workers
+        }
+    }
+}
+
+/// 마이크 환경에 따라 고르는 잡음 제거 강도
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Preset {
+    /// 감쇠량을 제한하고 후처리 필터를 끈다. 잡음이 적은 핀 마이크 등
+    Gentle,
+    #[default]
+    Default,
+    /// 잡음으로 보는 구간을 넓히고 후처리 필터를 강하게 건다. 카메라 내장 마이크 등
+    Aggressive,
+    Custom(Parameter),
+}
+
+impl Preset {
+    /// 프리셋에 해당하는 파라미터를 검증해서 돌려준다.
+    pub fn parameter(&self) -> Result<Parameter, DeepFilterNetError> {
+        let parameter = match *self {
+            Preset::Gentle => Parameter {
+                post_filter: false,
+                atten_lim_db: 12.0,
+                max_db_erb_thresh: 30.0,
+                max_db_df_thresh: 20.0,
+                ..Default::default()
+            },
+            Preset::Default => Parameter::default(),
+            Preset::Aggressive => Parameter {
+                post_filter_beta: 0.05,
+                min_db_thresh: -10.0,
+                max_db_erb_thresh: 40.0,
+                max_db_df_thresh: 40.0,
+                ..Default::default()
+            },
+            Preset::Custom(parameter) => parameter,
+        };
+        parameter.validate()?;
+        Ok(parameter)
+    }
+}
+
+// 한 번에 읽어 들이는 입력 프레임 수
+const READ_BLOCK_FRAMES: usize = 48000;
+
+// 병렬 처리할 때 워커 하나가 맡는 기본 청크 길이
+const CHUNK_SEC: usize = 30;
+// 이웃 청크와 겹쳐 크로스페이드하는 길이 (모델 샘플레이트 기준 1/N초)
+const CROSSFADE_DIVISOR: usize = 2;
+// 청크마다 앞쪽을 더 처리해 모델 상태를 안정시킨 뒤 버리는 길이 (초)
+const PRE_ROLL_SEC: usize = 1;
+
+/// 파일을 거치지 않고 잡음을 제거할 입력. blocks는 교차 배치된 샘플 블록을 차례로 돌려준다.
+pub struct SampleStream<I> {
+    pub sample_rate: u32,
+    pub channels: usize,
+    /// 전체 프레임 수. 어림값이어도 되며, 진행률 표시와 워커 수 결정에만 쓴다.
+    pub frames: Option<usize>,
+    pub blocks: I,
+}
+
+// WAV 파일을 READ_BLOCK_FRAMES 단위의 [-1, 1] 범위 블록으로 읽는다.
+fn wav_blocks<'a>(
+    reader: &'a mut WavReader<BufReader<File>>,
+) -> SampleStream<impl Iterator<Item = Result<Vec<f32>, DeepFilterNetError>> + 'a> {
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+    let frames = reader.duration() as usize;
+    let scale = (1_i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+    let mut samples: Box<dyn Iterator<Item = Result<f32, hound::Error>> + 'a> =
+        match spec.sample_format {
+            hound::SampleFormat::Float => Box::new(reader.samples::<f32>()),
+            hound::SampleFormat::Int => Box::new(
+                reader
+                    .samples::<i32>()
+                    .map(move |sample| sample.map(|sample| sample as f32 / scale)),
+            ),
+        };
+
+    let blocks = std::iter::from_fn(move || {
+        let block: Result<Vec<f32>, _> = samples
+            .by_ref()
+            .take(READ_BLOCK_FRAMES * channels)
+            .collect();
+        match block {
+            Ok(block) if block.is_empty() => None,
+            block => Some(block.map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))),
+        }
+    });
+
+    SampleStream {
+        sample_rate: spec.sample_rate,
+        channels,
+        frames: Some(frames),
+        blocks,
+    }
+}
+
+// 입력 블록을 읽어 채널별 모델 샘플레이트 샘플로 변환한다.
+struct ModelRateReader<I> {
+    blocks: I,
+    resamplers: Vec<Resampler>,
+    channels: usize,
+    // 지금까지 읽은 입력 프레임 수
+    frames: usize,
+    // 모델 샘플레이트 기준 전체 길이 (진행률 표시용, 모르면 0)
+    total: usize,
+}
+
+impl<I> ModelRateReader<I>
+where
+    I: Iterator<Item = Result<Vec<f32>, DeepFilterNetError>>,
+{
+    fn new(blocks: I, sample_rate: u32, channels: usize, sr: usize, total: usize) -> Self {
+        Self {
+            blocks,
+            resamplers: (0..channels)
+                .map(|_| Resampler::new(sample_rate as usize, sr))
+                .collect(),
+            channels,
+            frames: 0,
+            total,
+        }
+    }
+
+    // 다음 블록을 읽어 noisy에 덧붙인다. 입력이 끝났으면 true를 돌려준다.
+    fn read_block(&mut self, noisy: &mut [Vec<f32>]) -> Result<bool, DeepFilterNetError> {
+        let Some(block) = self.blocks.next() else {
+            for (resampler, channel) in self.resamplers.iter_mut().zip(noisy.iter_mut()) {
+                resampler.flush(channel);
+            }
+            return Ok(true);
+        };
+        let block = block?;
+        self.frames += block.len() / self.channels;
+
+        // 채널별로 나눠 모델 샘플레이트로 변환
+        for (ch, resampler) in self.resamplers.iter_mut().enumerate() {
+            let channel: Vec<f32> = block
+                .iter()
+                .skip(ch)
+                .step_by(self.channels)
+                .copied()
+                .collect();
+            resampler.process(&channel, &mut noisy[ch]);
+        }
+        Ok(false)
+    }
+}
+
+// 출력 샘플을 교차 배치해 받는 곳
+type SampleSink<'a> = dyn FnMut(&[f32]) -> Result<(), DeepFilterNetError> + 'a;
+
+// 채널별 모델 샘플레이트 출력을 원래 샘플레이트로 되돌려 교차 배치한 뒤 sink로 넘긴다.
+// 리샘플링 반올림과 관계없이 입력과 같은 수의 프레임을 내보낸다.
+struct ModelRateWriter<'a, 'b> {
+    sink: &'a mut SampleSink<'b>,
+    resamplers: Vec<Resampler>,
+    pending: Vec<Vec<f32>>,
+    written: usize,
+    block: Vec<f32>,
+}
+
+impl<'a, 'b> ModelRateWriter<'a, 'b> {
+    fn new(sink: &'a mut SampleSink<'b>, sr: usize, sample_rate: u32, channels: usize) -> Self {
+        Self {
+            sink,
+            resamplers: (0..channels)
+                .map(|_| Resampler::new(sr, sample_rate as usize))
+                .collect(),
+            pending: vec![Vec::new(); channels],
+            written: 0,
+            block: Vec::new(),
+        }
+    }
+
+    // limit은 지금까지 읽은 입력 프레임 수로, 출력이 이를 넘지 않게 한다.
+    fn write<S: AsRef<[f32]>>(
+        &mut self,
+        enhanced: &[S],
+        limit: usize,
+    ) -> Result<(), DeepFilterNetError> {
+        for ((resampler, pending), channel) in self
+            .resamplers
+            .iter_mut()
+            .zip(self.pending.iter_mut())
+            .zip(enhanced)
+        {
+            resampler.process(channel.as_ref(), pending);
+        }
+        self.write_pending(limit)
+    }
+
+    fn finish(mut self, frames: usize) -> Result<(), DeepFilterNetError> {
+        let remaining = frames.saturating_sub(self.written);
+        for (resampler, pending) in self.resamplers.iter_mut().zip(self.pending.iter_mut()) {
+            resampler.flush(pending);
+            // 모자란 프레임은 0으로 채운다.
+            if pending.len() < remaining {
+                pending.resize(remaining, 0.0);
+            }
+        }
+        self.write_pending(frames)
+    }
+
+    // 모든 채널에 있는 프레임만큼 꺼내 교차 배치해 넘긴다.
+    fn write_pending(&mut self, limit: usize) -> Result<(), DeepFilterNetError> {
+        let available = self.pending.iter().map(Vec::len).min().unwrap_or(0);
+        let frames = available.min(limit.saturating_sub(self.written));
+        self.written += frames;
+        self.block.clear();
+        for i in 0..frames {
+            for channel in self.pending.iter() {
+                self.block.push(channel[i]);
+            }
+        }
+        if !self.block.is_empty() {
+            (self.sink)(&self.block)?;
+        }
+        for channel in self.pending.iter_mut() {
+            channel.drain(..available);
+        }
+        Ok(())
+    }
+}
+
+// 입력을 블록 단위로 읽어 hop 단위로 처리해 바로 쓴다.
+// 메모리에는 읽기 블록 하나와 hop 몇 개 분량만 남는다.
+// 마지막에는 지연만큼 0을 붙여 입력의 끝까지 모델을 통과시키므로 출력 길이는 입력과 같다.
+fn denoise_stream<I, F>(
+    model: &mut DfTract,
+    mut reader: ModelRateReader<I>,
+    mut writer: ModelRateWriter,
+    delay: usize,
+    cancel: &CancellationToken,
+    progress_callback: &mut F,
+) -> Result<(), DeepFilterNetError>
+where
+    I: Iterator<Item = Result<Vec<f32>, DeepFilterNetError>>,
+    F: FnMut(f32),
+{
+    let hop_size = model.hop_size;
+    let channels = reader.channels;
+
+    // 총 처리할 청크 수 계산. 전체 길이를 모르면(0) 진행률을 알리지 않는다.
+    let total_chunks = reader.total / hop_size;
+    let mut progress_counter = 0;
+
+    let mut noisy: Vec<Vec<f32>> = vec![Vec::new(); channels];
+    let mut enhanced: Vec<Vec<f32>> = vec![Vec::new(); channels];
+    let mut noisy_hop = Array2::<f32>::zeros((channels, hop_size));
+    let mut enhanced_hop = Array2::<f32>::zeros((channels, hop_size));
+    let mut skip = delay;
+    // 모델 샘플레이트 기준으로 받은 입력 수와 내보낸 출력 수
+    let mut received = 0;
+    let mut emitted = 0;
+
+    loop {
+        if cancel.is_cancelled() {
+            return Err(DeepFilterNetError::Cancelled);
+        }
+
+        let buffered = noisy[0].len();
+        let finished = reader.read_block(&mut noisy)?;
+        received += noisy[0].len() - buffered;
+
+        if finished {
+            // 남은 입력과 지연만큼의 출력이 모두 나오도록 0을 붙여 hop 단위로 맞춘다.
+            let padded = (noisy[0].len() + delay).div_ceil(hop_size) * hop_size;
+            for channel in noisy.iter_mut() {
+                channel.resize(padded, 0.0);
+            }
+        }
+
+        // hop 단위로 청크 처리
+        let hops = noisy[0].len() / hop_size;
+        for hop in 0..hops {
+            let range = hop * hop_size..(hop + 1) * hop_size;
+            for (ch, channel) in noisy.iter().enumerate() {
+                noisy_hop
+                    .row_mut(ch)
+                    .assign(&ArrayView1::from(&channel[range.clone()]));
+            }
+            model
+                .process(noisy_hop.view(), enhanced_hop.view_mut())
+                .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+
+            // 지연 보정: 출력 앞부분의 delay 샘플은 버린다.
+            let start = skip.min(hop_size);
+            skip -= start;
+            for (ch, channel) in enhanced.iter_mut().enumerate() {
+                channel.extend(enhanced_hop.row(ch).iter().skip(start));
+            }
+
+            // 진행률 업데이트 및 표시 (10% 단위로 표시)
+            progress_counter += 1;
+            let progress_percent =
+                (progress_counter as f32 / total_chunks.max(1) as f32 * 100.0).min(100.0);
+
+            if total_chunks > 0
+                && (progress_counter == 1
+                    || progress_counter % (total_chunks / 10).max(1) == 0
+                    || progress_counter == total_chunks)
+            {
+                progress_callback(progress_percent);
+            }
+        }
+        for channel in noisy.iter_mut() {
+            channel.drain(..hops * hop_size);
+        }
+
+        // 패딩으로 입력보다 길게 나온 부분은 버린다.
+        for channel in enhanced.iter_mut() {
+            channel.truncate(received - emitted);
+        }
+        emitted += enhanced[0].len();
+
+        writer.write(&enhanced, reader.frames)?;
+        enhanced.iter_mut().for_each(Vec::clear);
+
+        if finished {
+            writer.finish(reader.frames)?;
+            return Ok(());
+        }
+    }
+}
+
+// 독립된 청크 하나를 처리한다. 끝에 지연만큼 0을 붙여 마지막 샘플까지 모델을 통과시키고,
+// 지연과 앞쪽 pre_roll을 잘라 input[pre_roll..]과 같은 길이의 출력을 돌려준다.
+fn denoise_chunk(
+    model: &mut DfTract,
+    input: &[&[f32]],
+    delay: usize,
+    pre_roll: usize,
+    cancel: &CancellationToken,
+) -> Result<Vec<Vec<f32>>, DeepFilterNetError> {
+    let channels = input.len();
+    let length = input[0].len();
+    let hop_size = model.hop_size;
+    let hops = (length + delay).div_ceil(hop_size);
+
+    let mut output = vec![Vec::with_capacity(hops * hop_size); channels];
+    let mut noisy_hop = Array2::<f32>::zeros((channels, hop_size));
+    let mut enhanced_hop = Array2::<f32>::zeros((channels, hop_size));
+    for hop in 0..hops {
+        if cancel.is_cancelled() {
+            return Err(DeepFilterNetError::Cancelled);
+        }
+        for (ch, channel) in input.iter().enumerate() {
+            for (i, sample) in noisy_hop.row_mut(ch).iter_mut().enumerate() {
+                *sample = channel.get(hop * hop_size + i).copied().unwrap_or(0.0);
+            }
+        }
+        model
+            .process(noisy_hop.view(), enhanced_hop.view_mut())
+            .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+        for (ch, channel) in output.iter_mut().enumerate() {
+            channel.extend(enhanced_hop.row(ch).iter());
+        }
+    }
+
+    for channel in output.iter_mut() {
+        channel.drain(..delay + pre_roll);
+        channel.truncate(length - pre_roll);
+    }
+    Ok(output)
+}
+
+// chunk(모델 샘플 수) 길이의 겹치는 청크로 나눠 워커마다 다른 모델로 병렬 처리하고,
+// 겹친 구간은 크로스페이드해 잇는다. 한 번에 워커 수만큼의 청크만 메모리에 올린다.
+fn denoise_parallel<I, F>(
+    models: &mut [DfTract],
+    mut reader: ModelRateReader<I>,
+    mut writer: ModelRateWriter,
+    chunk: usize,
+    delay: usize,
+    cancel: &CancellationToken,
+    progress_callback: &mut F,
+) -> Result<(), DeepFilterNetError>
+where
+    I: Iterator<Item = Result<Vec<f32>, DeepFilterNetError>>,
+    F: FnMut(f32),
+{
+    let sr = models[0].sr;
+    let overlap = (sr / CROSSFADE_DIVISOR).min(chunk);
+    let pre_roll = PRE_ROLL_SEC * sr;
+    let channels = reader.channels;
+
+    // noisy[ch][0]의 모델 샘플레이트 기준 위치
+    let mut buffer_start = 0;
+    let mut noisy: Vec<Vec<f32>> = vec![Vec::new(); channels];
+    let mut next_chunk = 0;
+    // 이전 청크 출력 중 다음 청크와 겹치는 부분
+    let mut tail: Vec<Vec<f32>> = vec![Vec::new(); channels];
+    let mut finished = false;
+
+    loop {
+        // 워커 수만큼의 청크가 모일 때까지 읽는다.
+        while !finished
+            && buffer_start + noisy[0].len() < next_chunk + models.len() * chunk + overlap
+        {
+            finished = reader.read_block(&mut noisy)?;
+        }
+        let buffer_end = buffer_start + noisy[0].len();
+
+        // (시작, 끝) 구간. 마지막 청크가 아니면 끝에 overlap만큼 더 붙는다.
+        let mut ranges = Vec::new();
+        let mut start = next_chunk;
+        while ranges.len() < models.len() && start < buffer_end {
+            let end = if start + chunk + overlap <= buffer_end {
+                start + chunk + overlap
+            } else if finished {
+                buffer_end
+            } else {
+                break;
+            };
+            ranges.push((start, end));
+            start = (start + chunk).min(end);
+        }
+        if ranges.is_empty() {
+            break;
+        }
+
+        let outputs = std::thread::scope(|scope| {
+            let handles: Vec<_> = models
+                .iter_mut()
+                .zip(&ranges)
+                .map(|(model, &(start, end))| {
+                    let from = start.saturating_sub(pre_roll).max(buffer_start);
+                    let input: Vec<&[f32]> = noisy
+                        .iter()
+                        .map(|channel| &channel[from - buffer_start..end - buffer_start])
+                        .collect();
+                    scope.spawn(move || denoise_chunk(model, &input, delay, start - from, cancel))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(DeepFilterNetError::InputNotValid(
+                            "DeepFilterNet worker panicked".to_string(),
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        for (&(start, end), mut output) in ranges.iter().zip(outputs) {
+            // 앞 청크의 꼬리에서 이번 청크로 선형 크로스페이드
+            let fade = tail[0].len();
+            for (channel, tail) in output.iter_mut().zip(&tail) {
+                for (i, (sample, previous)) in channel.iter_mut().zip(tail).enumerate() {
+                    let weight = (i as f32 + 0.5) / fade as f32;
+                    *sample = previous * (1.0 - weight) + *sample * weight;
+                }
+            }
+
+            let emit = if end - start > chunk {
+                chunk
+            } else {
+                end - start
+            };
+            for (channel, tail) in output.iter_mut().zip(tail.iter_mut()) {
+                *tail = channel.split_off(emit);
+            }
+            writer.write(&output, reader.frames)?;
+            next_chunk = start + emit;
+        }
+
+        // 다음 청크의 pre_roll에 필요한 부분만 남긴다.
+        let keep_from = next_chunk.saturating_sub(pre_roll).max(buffer_start);
+        for channel in noisy.iter_mut() {
+            channel.drain(..keep_from - buffer_start);
+        }
+        buffer_start = keep_from;
+
+        // 전체 길이를 모르면(0) 진행률을 알리지 않는다.
+        if reader.total > 0 {
+            progress_callback((next_chunk as f32 / reader.total as f32 * 100.0).min(100.0));
+        }
+    }
+
+    writer.finish(reader.frames)?;
+    Ok(())
+}
+
+pub fn apply_deepfilternet<F>(
+    params: Parameter,
+    model_path: &str,
+    audio_path: &str,
+    output_path: &str,
+    cancel: &CancellationToken,
+    progress_callback: F,
+) -> Result<(), DeepFilterNetError>
+where
+    F: FnMut(f32) + Send + Sync + 'static,
+{
+    params.validate()?;
+
+    let df_params = match DfParams::new(model_path.into()) {
+        Ok(df) => df,
+        Err(e) => return Err(DeepFilterNetError::InputNotValid(e.to_string())),
+    };
+    denoise_file(
+        params,
+        df_params,
+        audio_path,
+        output_path,
+        cancel,
+        progress_callback,
+    )
+}
+
+/// 파일을 거치지 않고 입력 블록의 잡음을 제거한다.
+/// 출력은 입력과 샘플레이트, 채널 구성, 길이가 같으며 교차 배치된 블록으로 on_output에 넘긴다.
+pub fn denoise_samples<I, O, F>(
+    params: Parameter,
+    model_path: &str,
+    input: SampleStream<I>,
+    mut on_output: O,
+    cancel: &CancellationToken,
+    mut progress_callback: F,
+) -> Result<(), DeepFilterNetError>
+where
+    I: Iterator<Item = Vec<f32>>,
+    O: FnMut(&[f32]),
+    F: FnMut(f32),
+{
+    params.validate()?;
+
+    let df_params = DfParams::new(model_path.into())
+        .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+    let SampleStream {
+        sample_rate,
+        channels,
+        frames,
+        blocks,
+    } = input;
+    denoise(
+        params,
+        df_params,
+        CHUNK_SEC,
+        SampleStream {
+            sample_rate,
+            channels,
+            frames,
+            blocks: blocks.map(Ok),
+        },
+        &mut |samples| {
+            on_output(samples);
+            Ok(())
+        },
+        cancel,
+        &mut progress_callback,
+    )
+}
+
+// 출력 파일은 입력과 샘플레이트, 채널 수, 길이(프레임 수)가 같다.
+fn denoise_file<F>(
+    params: Parameter,
+    df_params: DfParams,
+    audio_path: &str,
+    output_path: &str,
+    cancel: &CancellationToken,
+    mut progress_callback: F,
+) -> Result<(), DeepFilterNetError>
+where
+    F: FnMut(f32),
+{
+    let mut reader = WavReader::open(audio_path)
+        .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+    let spec = reader.spec();
+
+    // 입력과 출력이 같은 파일일 수 있으므로 임시 파일에 쓴 뒤 교체한다.
+    let temp_path = format!("{}.denoising", output_path);
+    // 출력은 입력과 같은 형식(비트 깊이, 정수/실수)으로 쓴다.
+    let mut writer = create_wav(&temp_path, spec)
+        .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+
+    let result = denoise(
+        params,
+        df_params,
+        CHUNK_SEC,
+        wav_blocks(&mut reader),
+        &mut |samples| {
+            write_samples(&mut writer, samples)
+                .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))
+        },
+        cancel,
+        &mut progress_callback,
+    );
+    let result = result.and_then(|()| {
+        finalize_wav(writer).map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))
+    });
+    drop(reader);
+
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    std::fs::rename(&temp_path, output_path)
+        .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))
+}
+
+// 입력을 모델 샘플레이트로 바꿔 잡음을 제거한 뒤 원래 샘플레이트로 되돌려 sink로 넘긴다.
+// 병렬 처리할 때는 chunk_sec 길이의 청크로 나눈다.
+fn denoise<I, F>(
+    params: Parameter,
+    df_params: DfParams,
+    chunk_sec: usize,
+    input: SampleStream<I>,
+    sink: &mut SampleSink,
+    cancel: &CancellationToken,
+    progress_callback: &mut F,
+) -> Result<(), DeepFilterNetError>
+where
+    I: Iterator<Item = Result<Vec<f32>, DeepFilterNetError>>,
+    F: FnMut(f32),
+{
+    let SampleStream {
+        sample_rate,
+        channels,
+        frames,
+        blocks,
+    } = input;
+    if channels == 0 || sample_rate == 0 {
+        return Err(DeepFilterNetError::InputNotValid(
+            "Audio has no channels or sample rate".to_string(),
+        ));
+    }
+
+    let mut r_params = RuntimeParams::default_with_ch(channels);
+    r_params = r_params
+        .with_atten_lim(params.atten_lim_db)
+        .with_thresholds(
+            params.min_db_thresh,
+            params.max_db_erb_thresh,
+            params.max_db_df_thresh,
+        );
+    if params.post_filter {
+        r_params = r_params.with_post_filter(params.post_filter_beta);
+    }
+    if let Ok(red) = params.reduce_mask.try_into() {
+        r_params = r_params.with_mask_reduce(red);
+    } else {
+        return Err(DeepFilterNetError::InputNotValid(
+            "invalid reduce_mask".to_string(),
+        ));
+    }
+
+    let mut model: DfTract = DfTract::new(df_params.clone(), &r_params)
+        .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?;
+    let sr = model.sr;
+    let mut delay = model.fft_size - model.hop_size; // STFT delay
+    delay += model.lookahead * model.hop_size; // Add model latency due to lookahead
+    let delay = if params.compensate_delay { delay } else { 0 };
+
+    // 모델 샘플레이트 기준 전체 길이 (진행률 표시와 병렬 처리 여부 판단용, 모르면 0)
+    let total = frames.map_or(0, |frames| frames * sr / sample_rate as usize);
+    // 청크 하나보다 짧으면 나눠도 이득이 없다.
+    let chunk = chunk_sec.max(1) * sr;
+    let workers = match frames {
+        Some(_) => params.worker_count().min(total.div_ceil(chunk)),
+        None => params.worker_count(),
+    }
+    .max(1);
+
+    let reader = ModelRateReader::new(blocks, sample_rate, channels, sr, total);
+    let writer = ModelRateWriter::new(sink, sr, sample_rate, channels);
+
+    if workers > 1 {
+        // 워커마다 상태를 따로 가지는 모델을 만든다.
+        let mut models = vec![model];
+        for _ in 1..workers {
+            models.push(
+                DfTract::new(df_params.clone(), &r_params)
+                    .map_err(|e| DeepFilterNetError::InputNotValid(e.to_string()))?,
+            );
+        }
+        denoise_parallel(
+            &mut models,
+            reader,
+            writer,
+            chunk,
+            delay,
+            cancel,
+            progress_callback,
+        )
+    } else {
+        denoise_stream(&mut model, reader, writer, delay, cancel, progress_callback)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in [Preset::Gentle, Preset::Default, Preset::Aggressive] {
+            assert!(preset.parameter().is_ok());
+        }
+
+        let invalid = Preset::Custom(Parameter {
+            min_db_thresh: 40.0,
+            ..Default::default()
+        });
+        assert!(matches!(
+            invalid.parameter(),
+            Err(DeepFilterNetError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_output_length_matches_input() {
+        let dir = std::env::temp_dir();
+        // hop 크기의 배수가 아니고, 모델(48kHz)과 샘플레이트나 비트 깊이가 다른 입력
+        for (sample_rate, frames, bits_per_sample) in
+            [(48000, 48000 + 123, 16), (44100, 44100 + 7, 24)]
+        {
+            let input_path = dir.join(format!(
+                "test_output_length_matches_input_{}_{}.wav",
+                std::process::id(),
+                sample_rate
+            ));
+            let output_path = dir.join(format!(
+                "test_output_length_matches_input_{}_{}_out.wav",
+                std::process::id(),
+                sample_rate
+            ));
+
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate,
+                bits_per_sample,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let max = ((1_i32 << (bits_per_sample - 1)) - 1) as f32;
+            let mut writer = hound::WavWriter::create(&input_path, spec).unwrap();
+            for i in 0..frames {
+                let t = i as f32 / sample_rate as f32;
+                let sample = 0.3 * (t * 440.0 * std::f32::consts::TAU).sin();
+                writer.write_sample((sample * max) as i32).unwrap();
+            }
+            writer.finalize().unwrap();
+
+            denoise_file(
+                Parameter {
+                    workers: 1,
+                    ..Default::default()
+                },
+                DfParams::default(),
+                input_path.to_str().unwrap(),
+                output_path.to_str().unwrap(),
+                &CancellationToken::new(),
+                |_| {},
+            )
+            .unwrap();
+
+            let reader = WavReader::open(&output_path).unwrap();
+            assert_eq!(reader.spec(), spec);
+            assert_eq!(reader.duration(), frames as u32);
+        }
+    }
+
+    #[test]
+    fn test_stream_output_length_matches_input() {
+        // 길이를 모르는 입력을 크기가 제각각인 블록으로 넘기고, 병렬 처리 경로도 거친다.
+        let frames = 48000 * 2 + 77;
+        let input: Vec<f32> = (0..frames)
+            .map(|i| 0.3 * (i as f32 / 48000.0 * 440.0 * std::f32::consts::TAU).sin())
+            .collect();
+        for workers in [1, 2] {
+            let mut output = Vec::new();
+            denoise(
+                Parameter {
+                    workers,
+                    ..Default::default()
+                },
+                DfParams::default(),
+                CHUNK_SEC,
+                SampleStream {
+                    sample_rate: 48000,
+                    channels: 1,
+                    frames: None,
+                    blocks: input.chunks(1001).map(|block| Ok(block.to_vec())),
+                },
+                &mut |samples| {
+                    output.extend_from_slice(samples);
+                    Ok(())
+                },
+                &CancellationToken::new(),
+                &mut |_| {},
+            )
+            .unwrap();
+
+            assert_eq!(output.len(), frames);
+        }
+    }
+
+    #[test]
+    fn test_parallel_chunks_match_single_worker() {
+        // 1초 청크로 나누면 워커 2개가 두 번씩, 4개의 청크를 처리한다.
+        let frames = 48000 * 3 + 24000 + 77;
+        let input: Vec<f32> = (0..frames)
+            .map(|i| {
+                let t = i as f32 / 48000.0;
+                let noise = ((i as u32).wrapping_mul(2_654_435_761) >> 16) as f32 / 65536.0 - 0.5;
+                0.3 * (t * 220.0 * std::f32::consts::TAU).sin() * (t * 3.0).sin().abs()
+                    + 0.02 * noise
+            })
+            .collect();
+
+        let outputs: Vec<Vec<f32>> = [1, 2]
+            .into_iter()
+            .map(|workers| {
+                let mut output = Vec::new();
+                denoise(
+                    Parameter {
+                        workers,
+                        ..Default::default()
+                    },
+                    DfParams::default(),
+                    1,
+                    SampleStream {
+                        sample_rate: 48000,
+                        channels: 1,
+                        frames: Some(frames),
+                        blocks: input.chunks(4801).map(|block| Ok(block.to_vec())),
+                    },
+                    &mut |samples| {
+                        output.extend_from_slice(samples);
+                        Ok(())
+                    },
+                    &CancellationToken::new(),
+                    &mut |_| {},
+                )
+                .unwrap();
+                output
+            })
+            .collect();
+
+        assert_eq!(outputs[0].len(), frames);
+        assert_eq!(outputs[1].len(), frames);
+
+        // 청크 경계에서도 모델 상태가 안정된 뒤 크로스페이드하므로 한 워커로 처리한 결과와 거의 같아야 한다.
+        let rms = |values: &mut dyn Iterator<Item = f32>| {
+            let (sum, count) = values.fold((0.0_f64, 0), |(sum, count), v| {
+                (sum + (v as f64).powi(2), count + 1)
+            });
+            (sum / count as f64).sqrt()
+        };
+        let reference = rms(&mut outputs[0].iter().copied());
+        let difference = rms(&mut outputs[0].iter().zip(&outputs[1]).map(|(a, b)| a - b));
+        assert!(
+            difference <= reference * 0.1 + 1e-4,
+            "{} vs {}",
+            difference,
+            reference
+        );
+    }
+}
@@ -9,6 +9,10 @@ use super::analyzer::Segment;
 pub enum FcpXmlError {
     #[error("Failed to generate FCP XML: {0}")]
     GenerateError(String),
+    #[error("Unsupported FPS: {0}")]
+    UnsupportedFps(String),
+    #[error("Unsupported resolution: {0}")]
+    UnsupportedResolution(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,10 +134,36 @@ pub struct Setting {
     pub resolution: Resolution,
 }
 
+impl Setting {
+    /// "29.97", "30", "59.94", "60"과 "SD", "HD", "FHD", "4K" 형식의 문자열로 설정을 만든다.
+    pub fn parse(fps: &str, resolution: &str) -> Result<Self, FcpXmlError> {
+        let fps = match fps {
+            "29.97" => FPS::FPS29_97,
+            "30" => FPS::FPS30,
+            "59.94" => FPS::FPS59_94,
+            "60" => FPS::FPS60,
+            _ => return Err(FcpXmlError::UnsupportedFps(fps.to_string())),
+        };
+
+        let resolution = match resolution {
+            "SD" => Resolution::SD,
+            "HD" => Resolution::HD,
+            "FHD" => Resolution::FHD,
+            "4K" => Resolution::FourK,
+            _ => return Err(FcpXmlError::UnsupportedResolution(resolution.to_string())),
+        };
+
+        Ok(Self { fps, resolution })
+    }
+}
+
+/// connected_audio_path가 있으면 각 클립 아래에 같은 구간의 오디오(잡음 제거본 등)를
+/// 연결 클립으로 붙인다.
 pub fn generate_fcpxml(
     setting: Setting,
     input_video_path: &str,
     silent_segments: Vec<Segment>,
+    connected_audio_path: Option<&str>,
     output_path: &str,
 ) -> Result<String, FcpXmlError> {
     let mut xml = xml_builder::XMLBuilder::new()
@@ -179,6 +209,25 @@ pub fn generate_fcpxml(
         .add_child(asset)
         .map_err(|e| FcpXmlError::GenerateError(e.to_string()))?;
 
+    if let Some(connected_audio_path) = connected_audio_path {
+        let mut audio_asset = xml_builder::XMLElement::new("asset");
+        audio_asset.add_attribute("id", "r3");
+        audio_asset.add_attribute("name", "Audio");
+        audio_asset.add_attribute("hasVideo", "0");
+        audio_asset.add_attribute("hasAudio", "1");
+
+        let mut media_rep = xml_builder::XMLElement::new("media-rep");
+        media_rep.add_attribute("kind", "original-media");
+        media_rep.add_attribute("src", connected_audio_path);
+
+        audio_asset
+            .add_child(media_rep)
+            .map_err(|e| FcpXmlError::GenerateError(e.to_string()))?;
+        resources
+            .add_child(audio_asset)
+            .map_err(|e| FcpXmlError::GenerateError(e.to_string()))?;
+    }
+
     // event
     let mut event = xml_builder::XMLElement::new("event");
     event.add_attribute("name", "Clipping");
@@ -222,6 +271,33 @@ pub fn generate_fcpxml(
             setting.fps.get_start_timecode(start_frame).as_str(),
         );
 
+        // 연결 클립의 offset은 부모 클립의 시간 기준이므로 부모의 start와 같으면 처음에 맞춰진다.
+        // 원본 오디오와 겹쳐 들리지 않도록 부모 클립은 영상만 쓴다.
+        if connected_audio_path.is_some() {
+            asset_clip.add_attribute("srcEnable", "video");
+
+            let mut audio_clip = xml_builder::XMLElement::new("asset-clip");
+            audio_clip.add_attribute("ref", "r3");
+            audio_clip.add_attribute("lane", "-1");
+            audio_clip.add_attribute(
+                "offset",
+                setting.fps.get_start_timecode(start_frame).as_str(),
+            );
+            audio_clip.add_attribute("name", "Audio");
+            audio_clip.add_attribute(
+                "duration",
+                setting.fps.get_duration_timecode(duration_frame).as_str(),
+            );
+            audio_clip.add_attribute(
+                "start",
+                setting.fps.get_start_timecode(start_frame).as_str(),
+            );
+
+            asset_clip
+                .add_child(audio_clip)
+                .map_err(|e| FcpXmlError::GenerateError(e.to_string()))?;
+        }
+
         current_frame_offset += duration_frame;
 
         spine
@@ -259,3 +335,36 @@ pub fn generate_fcpxml(
 
     Ok(output_path.to_string())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_connected_audio_clip() {
+        let output_path = std::env::temp_dir().join(format!(
+            "test_connected_audio_clip_{}.fcpxml",
+            std::process::id()
+        ));
+        let setting = Setting {
+            fps: FPS::FPS30,
+            resolution: Resolution::FHD,
+        };
+        let segments = vec![Segment::new(1.0, 2.0), Segment::new(3.0, 3.5)];
+
+        generate_fcpxml(
+            setting,
+            "/video.mov",
+            segments,
+            Some("/video.denoised.wav"),
+            output_path.to_str().unwrap(),
+        )
+        .unwrap();
+
+        let xml = std::fs::read_to_string(&output_path).unwrap();
+        assert!(xml.contains(r#"src="/video.denoised.wav""#));
+        // 클립마다 같은 구간을 가리키는 연결 클립이 하나씩 붙는다.
+        assert_eq!(xml.matches(r#"lane="-1""#).count(), 2);
+        assert_eq!(xml.matches(r#"srcEnable="video""#).count(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+/// 2차 IIR 필터 (RBJ Audio EQ Cookbook)
+#[derive(Debug, Clone, Copy)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+pub const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+// 험 제거용 노치 필터의 Q (60Hz에서 대역폭 약 6Hz)
+const NOTCH_Q: f32 = 10.0;
+// 기본 주파수를 포함해 제거할 험 배음 수
+const HUM_HARMONICS: usize = 3;
+
+impl Biquad {
+    fn new(b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    // 나이퀴스트 주파수를 넘지 않도록 차단 주파수를 제한한다.
+    fn coefficients(sample_rate: u32, cutoff_hz: f32, q: f32) -> (f32, f32) {
+        let nyquist = sample_rate as f32 / 2.0;
+        let w0 = 2.0 * PI * cutoff_hz.clamp(1.0, nyquist * 0.99) / sample_rate as f32;
+        (w0.cos(), w0.sin() / (2.0 * q))
+    }
+
+    pub fn low_pass(sample_rate: u32, cutoff_hz: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::coefficients(sample_rate, cutoff_hz, q);
+        Self::new(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    pub fn high_pass(sample_rate: u32, cutoff_hz: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::coefficients(sample_rate, cutoff_hz, q);
+        Self::new(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    pub fn notch(sample_rate: u32, center_hz: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::coefficients(sample_rate, center_hz, q);
+        Self::new(1.0, -2.0 * cos, 1.0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+/// 음성 대역 필터의 범위
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BandPass {
+    pub low_hz: f32,
+    pub high_hz: f32,
+}
+
+impl Default for BandPass {
+    fn default() -> Self {
+        Self {
+            low_hz: 300.0,
+            high_hz: 3400.0,
+        }
+    }
+}
+
+/// RMS를 계산하기 전에 분석용 신호에 적용하는 필터. 기본값은 아무 필터도 적용하지 않는다.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PreFilterOption {
+    /// 이 주파수 아래의 저역 잡음(럼블, 팬 소음)을 제거한다. (Hz)
+    pub high_pass_hz: Option<f32>,
+    /// 음성 대역만 남긴다.
+    pub band_pass: Option<BandPass>,
+    /// 전원 험 주파수 (50 또는 60Hz). 배음까지 함께 제거한다.
+    pub notch_hz: Option<f32>,
+}
+
+impl PreFilterOption {
+    pub(crate) fn filters(&self, sample_rate: u32) -> Vec<Biquad> {
+        let mut filters = Vec::new();
+        if let Some(cutoff_hz) = self.high_pass_hz {
+            filters.push(Biquad::high_pass(sample_rate, cutoff_hz, BUTTERWORTH_Q));
+        }
+        if let Some(BandPass { low_hz, high_hz }) = self.band_pass {
+            filters.push(Biquad::high_pass(sample_rate, low_hz, BUTTERWORTH_Q));
+            filters.push(Biquad::low_pass(sample_rate, high_hz, BUTTERWORTH_Q));
+        }
+        if let Some(hum_hz) = self.notch_hz {
+            let nyquist = sample_rate as f32 / 2.0;
+            for harmonic in 1..=HUM_HARMONICS {
+                let center_hz = hum_hz * harmonic as f32;
+                if center_hz < nyquist {
+                    filters.push(Biquad::notch(sample_rate, center_hz, NOTCH_Q));
+                }
+            }
+        }
+        filters
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.high_pass_hz.is_none() && self.band_pass.is_none() && self.notch_hz.is_none()
+    }
+
+    /// 설정된 필터를 차례로 적용한다.
+    pub fn apply(&self, samples: &mut [f32], sample_rate: u32) {
+        let mut filters = self.filters(sample_rate);
+        for sample in samples.iter_mut() {
+            *sample = filters
+                .iter_mut()
+                .fold(*sample, |value, filter| filter.process(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sine_rms_db(option: &PreFilterOption, frequency: f32) -> f32 {
+        let sample_rate = 16000;
+        let mut samples: Vec<f32> = (0..sample_rate * 2)
+            .map(|i| (i as f32 / sample_rate as f32 * frequency * 2.0 * PI).sin())
+            .collect();
+        option.apply(&mut samples, sample_rate as u32);
+
+        // 필터가 안정된 뒤 1초만 측정
+        let settled = &samples[sample_rate..];
+        let mean_square = settled.iter().map(|s| s * s).sum::<f32>() / settled.len() as f32;
+        10.0 * mean_square.log10()
+    }
+
+    #[test]
+    fn test_pre_filter_removes_rumble_and_hum() {
+        let option = PreFilterOption {
+            high_pass_hz: Some(80.0),
+            notch_hz: Some(60.0),
+            ..Default::default()
+        };
+        let unfiltered = sine_rms_db(&PreFilterOption::default(), 1000.0);
+
+        // 험과 배음, 저역 럼블은 크게 줄고 음성 대역은 그대로 남는다.
+        assert!(sine_rms_db(&option, 60.0) < unfiltered - 30.0);
+        assert!(sine_rms_db(&option, 120.0) < unfiltered - 30.0);
+        assert!(sine_rms_db(&option, 20.0) < unfiltered - 20.0);
+        assert!((sine_rms_db(&option, 1000.0) - unfiltered).abs() < 0.5);
+    }
+}
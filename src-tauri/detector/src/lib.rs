@@ -0,0 +1,32 @@
+//! 오디오에서 소리가 있는 구간을 찾고 Final Cut Pro용 FCPXML로 내보내는 라이브러리.
+//!
+//! 기본 기능은 WAV 등 오디오 파일 분석만 포함한다. 외부 라이브러리가 필요한 기능은
+//! cargo feature로 켠다.
+//!
+//! - `ffmpeg`: 비디오에서 오디오 추출 ([`converter`], [`pipeline`])
+//! - `deepfilter`: DeepFilterNet 잡음 제거 ([`deepfilter`])
+//! - `fcpxml`: FCPXML 내보내기 ([`fcpxml`])
+
+pub mod analyzer;
+pub mod cancel;
+pub mod compressor;
+#[cfg(feature = "ffmpeg")]
+pub mod converter;
+#[cfg(feature = "deepfilter")]
+pub mod deepfilter;
+#[cfg(feature = "fcpxml")]
+pub mod fcpxml;
+pub mod filter;
+pub mod limiter;
+pub mod loudness;
+#[cfg(feature = "ffmpeg")]
+pub mod pipeline;
+pub mod resampler;
+pub mod vad;
+
+pub use analyzer::{
+    AudioAnalyzer, AudioAnalyzerError, AudioAnalyzerOption, AudioNormalizerOption, DetectionResult,
+    DetectorKind, NormalizationMode, NormalizationReport, OutputFormat, Progress, Region,
+    RegionKind, Segment, ThresholdMode,
+};
+pub use cancel::CancellationToken;
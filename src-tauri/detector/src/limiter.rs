@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::loudness::{TruePeakMeter, TRUE_PEAK_DELAY};
+
+/// 룩어헤드 브릭월 리미터 설정
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LimiterOption {
+    /// 출력 트루 피크 상한 (dBTP)
+    pub ceiling_db: f32,
+    /// 피크가 오기 전에 미리 게인을 줄이기 시작하는 시간 (ms)
+    pub lookahead_ms: f32,
+    /// 줄어든 게인이 원래대로 돌아오는 시간 (ms)
+    pub release_ms: f32,
+}
+
+impl Default for LimiterOption {
+    fn default() -> Self {
+        Self {
+            ceiling_db: -1.0,
+            lookahead_ms: 5.0,
+            release_ms: 50.0,
+        }
+    }
+}
+
+/// 프레임 단위로 샘플을 받는 룩어헤드 리미터.
+/// 트루 피크 필터 지연과 lookahead만큼 늦게 출력하며, 그 길이만큼의 메모리만 쓴다.
+pub struct Limiter {
+    channels: usize,
+    ceiling: f32,
+    lookahead: usize,
+    release: f32,
+    meters: Vec<TruePeakMeter>,
+    // 아직 출력하지 않은 입력 프레임 (채널 교차 배치)
+    delay: VecDeque<f32>,
+    // (프레임 번호, 필요 게인) 단조 덱. 앞이 lookahead 구간의 최솟값이다.
+    minimum: VecDeque<(usize, f32)>,
+    // 게인 변화를 부드럽게 만드는 이동 평균 구간
+    average: VecDeque<f32>,
+    sum: f64,
+    gain: f32,
+    min_gain: f32,
+    // 받은 프레임, 트루 피크 측정기에 넣은 프레임, 피크를 잰 프레임,
+    // 필요 게인을 넣은 프레임(끝의 채움 포함), 출력한 프레임 수
+    received: usize,
+    metered: usize,
+    measured: usize,
+    pushed: usize,
+    emitted: usize,
+    output: Vec<f32>,
+}
+
+impl Limiter {
+    pub fn new(channels: usize, sample_rate: u32, option: LimiterOption) -> Self {
+        let channels = channels.max(1);
+        let lookahead = ((sample_rate as f32 * option.lookahead_ms / 1000.0) as usize).max(1);
+        let release = if option.release_ms > 0.0 {
+            (-1000.0 / (sample_rate as f32 * option.release_ms)).exp()
+        } else {
+            0.0
+        };
+
+        Self {
+            channels,
+            ceiling: 10.0_f32.powf(option.ceiling_db.min(0.0) / 20.0),
+            lookahead,
+            release,
+            meters: vec![TruePeakMeter::new(); channels],
+            delay: VecDeque::with_capacity((lookahead + TRUE_PEAK_DELAY + 1) * channels),
+            minimum: VecDeque::with_capacity(lookahead + 1),
+            average: VecDeque::with_capacity(lookahead + 1),
+            sum: 0.0,
+            gain: 1.0,
+            min_gain: 1.0,
+            received: 0,
+            metered: 0,
+            measured: 0,
+            pushed: 0,
+            emitted: 0,
+            output: vec![0.0; channels],
+        }
+    }
+
+    /// 프레임 하나를 넣고, 출력할 차례가 된 이전 프레임이 있으면 게인을 적용해 돌려준다.
+    pub fn process(&mut self, frame: &[f32]) -> Option<&[f32]> {
+        self.delay.extend(frame.iter().copied().take(self.channels));
+        self.received += 1;
+        if self.meter(|ch| frame[ch]) {
+            Some(&self.output)
+        } else {
+            None
+        }
+    }
+
+    /// 입력이 끝난 뒤 남은 프레임을 하나씩 돌려준다. None이 나올 때까지 부른다.
+    pub fn flush(&mut self) -> Option<&[f32]> {
+        // 트루 피크 필터에 남은 샘플을 0으로 밀어낸 뒤, 모자란 lookahead 구간은 게인 1로 채운다.
+        while self.emitted < self.received {
+            let emitted = if self.measured < self.received {
+                self.meter(|_| 0.0)
+            } else {
+                self.push_required(1.0)
+            };
+            if emitted {
+                return Some(&self.output);
+            }
+        }
+        None
+    }
+
+    /// 지금까지의 최대 게인 감소량 (dB)
+    pub fn reduction_db(&self) -> f32 {
+        -20.0 * self.min_gain.log10()
+    }
+
+    // 채널마다 트루 피크 측정기에 샘플을 넣고, 피크가 나온 프레임의 필요 게인을 구한다.
+    fn meter(&mut self, sample: impl Fn(usize) -> f32) -> bool {
+        let mut peak = 0.0_f32;
+        for (ch, meter) in self.meters.iter_mut().enumerate() {
+            peak = peak.max(meter.process(sample(ch)));
+        }
+        self.metered += 1;
+        if self.metered <= TRUE_PEAK_DELAY {
+            return false;
+        }
+
+        let offset = (self.measured - self.emitted) * self.channels;
+        let peak = self
+            .delay
+            .range(offset..offset + self.channels)
+            .fold(peak, |peak, sample| peak.max(sample.abs()));
+        self.measured += 1;
+        let required = if peak > self.ceiling {
+            self.ceiling / peak
+        } else {
+            1.0
+        };
+        self.push_required(required)
+    }
+
+    // lookahead 구간의 필요 게인이 모두 모이면 가장 앞의 프레임을 출력한다.
+    fn push_required(&mut self, required: f32) -> bool {
+        while self
+            .minimum
+            .back()
+            .is_some_and(|&(_, value)| value >= required)
+        {
+            self.minimum.pop_back();
+        }
+        self.minimum.push_back((self.pushed, required));
+        self.pushed += 1;
+        if self.pushed < self.emitted + self.lookahead {
+            return false;
+        }
+        while self.minimum.front().is_some_and(|&(i, _)| i < self.emitted) {
+            self.minimum.pop_front();
+        }
+        let held = self.minimum[0].1;
+
+        // 게인 감소는 즉시, 복귀는 release 시간 상수로 천천히
+        self.gain = if held < self.gain {
+            held
+        } else {
+            held + (self.gain - held) * self.release
+        };
+
+        // lookahead 길이의 이동 평균으로 게인 변화를 부드럽게 만든다.
+        // 평균에 들어가는 값은 모두 현재 프레임의 필요 게인 이하이므로 상한을 넘지 않는다.
+        self.average.push_back(self.gain);
+        self.sum += self.gain as f64;
+        if self.average.len() > self.lookahead {
+            self.sum -= self.average.pop_front().unwrap_or(0.0) as f64;
+        }
+        let gain = (self.sum / self.average.len() as f64) as f32;
+        self.min_gain = self.min_gain.min(gain);
+
+        for (output, sample) in self
+            .output
+            .iter_mut()
+            .zip(self.delay.drain(..self.channels))
+        {
+            *output = sample * gain;
+        }
+        self.emitted += 1;
+        true
+    }
+}
+
+/// 채널이 교차 배치된 샘플의 트루 피크가 상한을 넘지 않도록 리미터를 적용하고,
+/// 최대 게인 감소량(dB)을 돌려준다. 스테레오 이미지가 흔들리지 않도록 모든 채널에 같은 게인을 적용한다.
+pub fn limit(samples: &mut [f32], channels: usize, sample_rate: u32, option: LimiterOption) -> f32 {
+    let channels = channels.max(1);
+    let frames = samples.len() / channels;
+    let mut limiter = Limiter::new(channels, sample_rate, option);
+    let mut frame = vec![0.0; channels];
+
+    // 출력은 입력보다 늦게 나오므로 같은 버퍼에 덮어써도 아직 읽지 않은 프레임을 건드리지 않는다.
+    let mut written = 0;
+    for i in 0..frames {
+        frame.copy_from_slice(&samples[i * channels..(i + 1) * channels]);
+        if let Some(output) = limiter.process(&frame) {
+            samples[written * channels..(written + 1) * channels].copy_from_slice(output);
+            written += 1;
+        }
+    }
+    while let Some(output) = limiter.flush() {
+        samples[written * channels..(written + 1) * channels].copy_from_slice(output);
+        written += 1;
+    }
+
+    limiter.reduction_db()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::loudness::true_peaks;
+
+    #[test]
+    fn test_limit_keeps_true_peak_under_ceiling() {
+        let sample_rate = 48000;
+        // 앞 1초는 상한을 6dB 넘는 사인파, 뒤 1초는 상한보다 작은 사인파
+        let mut samples: Vec<f32> = (0..sample_rate * 2)
+            .map(|i| {
+                let amplitude = if i < sample_rate { 1.78 } else { 0.25 };
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (t * 997.0 * std::f32::consts::TAU).sin()
+            })
+            .collect();
+        let original = samples.clone();
+
+        let option = LimiterOption::default();
+        let reduction_db = limit(&mut samples, 1, sample_rate as u32, option);
+
+        let ceiling = 10.0_f32.powf(option.ceiling_db / 20.0);
+        let max_peak = true_peaks(&samples).into_iter().fold(0.0, f32::max);
+        assert!(max_peak <= ceiling * 1.01);
+        assert!(reduction_db > 5.0);
+
+        // release 이후의 작은 소리는 그대로 남아야 한다.
+        let tail = sample_rate as usize + sample_rate as usize / 2;
+        for (limited, original) in samples[tail..].iter().zip(&original[tail..]) {
+            assert!((limited - original).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_limiter_streams_with_bounded_delay() {
+        let channels = 2;
+        let option = LimiterOption::default();
+        let mut limiter = Limiter::new(channels, 48000, option);
+        let ceiling = 10.0_f32.powf(option.ceiling_db / 20.0);
+
+        let mut output = Vec::new();
+        for i in 0..48000 {
+            let sample = 1.5 * (i as f32 / 48000.0 * 440.0 * std::f32::consts::TAU).sin();
+            if let Some(frame) = limiter.process(&[sample, -sample]) {
+                output.extend_from_slice(frame);
+            }
+            // 지연 버퍼는 입력 길이와 관계없이 lookahead 근처에 머문다.
+            assert!(limiter.delay.len() <= (limiter.lookahead + TRUE_PEAK_DELAY + 1) * channels);
+        }
+        while let Some(frame) = limiter.flush() {
+            output.extend_from_slice(frame);
+        }
+
+        assert_eq!(output.len(), 48000 * channels);
+        assert!(output.iter().all(|sample| sample.abs() <= ceiling * 1.01));
+    }
+}
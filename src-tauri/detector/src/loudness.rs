@@ -0,0 +1,405 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+// ITU-R BS.1770 / EBU R128 상수
+const LOUDNESS_OFFSET: f64 = -0.691;
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const INTEGRATED_RELATIVE_GATE_LU: f64 = -10.0;
+const RANGE_RELATIVE_GATE_LU: f64 = -20.0;
+
+// 100ms 단위 블록을 모아 momentary(400ms), short-term(3s) 구간을 만든다.
+const SUB_BLOCK_MS: u32 = 100;
+const MOMENTARY_SUB_BLOCKS: usize = 4;
+const SHORT_TERM_SUB_BLOCKS: usize = 30;
+
+// 트루 피크 측정용 4배 오버샘플링 보간 필터 (위상당 12탭, 총 48탭)
+const OVERSAMPLING: usize = 4;
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// 보간 필터의 지연 (원본 샘플 단위)
+pub const TRUE_PEAK_DELAY: usize = TRUE_PEAK_TAPS / 2;
+
+/// EBU R128 측정 결과
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LoudnessReport {
+    /// 통합 라우드니스 (LUFS)
+    pub integrated_lufs: f32,
+    /// 최대 momentary 라우드니스 (LUFS)
+    pub max_momentary_lufs: f32,
+    /// 최대 short-term 라우드니스 (LUFS)
+    pub max_short_term_lufs: f32,
+    /// 라우드니스 범위 (LU, EBU Tech 3342)
+    pub loudness_range_lu: f32,
+    /// 최대 샘플 레벨 (dBFS)
+    pub sample_peak_db: f32,
+    /// 최대 트루 피크 레벨 (dBTP)
+    pub true_peak_db: f32,
+}
+
+// K-weighting 필터 (고역 셸빙 + RLB 하이패스).
+// 차단 주파수가 매우 낮은 하이패스가 f32에서는 정밀도가 부족해 f64로 계산한다.
+#[derive(Debug, Clone, Copy)]
+struct KWeighting {
+    stages: [[f64; 5]; 2],
+    state: [[f64; 2]; 2],
+}
+
+impl KWeighting {
+    fn new(sample_rate: u32) -> Self {
+        let fs = sample_rate as f64;
+
+        // 1단계: 고역 셸빙 필터
+        let f0 = 1681.974450955533;
+        let gain_db = 3.999843853973347;
+        let q = 0.7071752369554196;
+        let k = (PI * f0 / fs).tan();
+        let vh = 10.0_f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+            2.0 * (k * k - 1.0) / a0,
+            (1.0 - k / q + k * k) / a0,
+        ];
+
+        // 2단계: RLB 하이패스 필터
+        let f0 = 38.13547087602444;
+        let q = 0.5003270373238773;
+        let k = (PI * f0 / fs).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = [
+            1.0,
+            -2.0,
+            1.0,
+            2.0 * (k * k - 1.0) / a0,
+            (1.0 - k / q + k * k) / a0,
+        ];
+
+        Self {
+            stages: [shelf, high_pass],
+            state: [[0.0; 2]; 2],
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f64 {
+        let mut value = input as f64;
+        for ([b0, b1, b2, a1, a2], [z1, z2]) in self.stages.iter().zip(self.state.iter_mut()) {
+            let output = b0 * value + *z1;
+            *z1 = b1 * value - a1 * output + *z2;
+            *z2 = b2 * value - a2 * output;
+            value = output;
+        }
+        value
+    }
+}
+
+// Hann 창을 씌운 sinc 함수를 위상별로 나누고, 각 위상의 DC 게인을 1로 맞춘다.
+fn interpolation_phases() -> [[f32; TRUE_PEAK_TAPS]; OVERSAMPLING] {
+    let length = OVERSAMPLING * TRUE_PEAK_TAPS;
+    let center = (length - 1) as f64 / 2.0;
+    let mut phases = [[0.0; TRUE_PEAK_TAPS]; OVERSAMPLING];
+
+    for i in 0..length {
+        let x = (i as f64 - center) / OVERSAMPLING as f64;
+        let sinc = if x == 0.0 {
+            1.0
+        } else {
+            (PI * x).sin() / (PI * x)
+        };
+        let window = 0.5 - 0.5 * (2.0 * PI * i as f64 / (length - 1) as f64).cos();
+        phases[i % OVERSAMPLING][i / OVERSAMPLING] = (sinc * window) as f32;
+    }
+
+    for phase in &mut phases {
+        let sum: f32 = phase.iter().sum();
+        phase.iter_mut().for_each(|tap| *tap /= sum);
+    }
+    phases
+}
+
+/// 오버샘플링으로 샘플 사이의 피크까지 측정한다. (ITU-R BS.1770-4 Annex 2)
+#[derive(Debug, Clone, Copy)]
+pub struct TruePeakMeter {
+    phases: [[f32; TRUE_PEAK_TAPS]; OVERSAMPLING],
+    history: [f32; TRUE_PEAK_TAPS],
+    peak: f32,
+}
+
+impl Default for TruePeakMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TruePeakMeter {
+    pub fn new() -> Self {
+        Self {
+            phases: interpolation_phases(),
+            history: [0.0; TRUE_PEAK_TAPS],
+            peak: 0.0,
+        }
+    }
+
+    /// 샘플 하나를 넣고, TRUE_PEAK_DELAY만큼 이전 위치의 보간 값 중 최대 절대값을 돌려준다.
+    pub fn process(&mut self, sample: f32) -> f32 {
+        self.history.copy_within(0..TRUE_PEAK_TAPS - 1, 1);
+        self.history[0] = sample;
+
+        let peak = self
+            .phases
+            .iter()
+            .map(|phase| {
+                phase
+                    .iter()
+                    .zip(&self.history)
+                    .map(|(tap, x)| tap * x)
+                    .sum::<f32>()
+                    .abs()
+            })
+            .fold(sample.abs(), f32::max);
+        self.peak = self.peak.max(peak);
+        peak
+    }
+
+    pub fn peak(&self) -> f32 {
+        self.peak
+    }
+}
+
+/// 샘플마다 주변의 트루 피크 값을 구한다. (필터 지연은 보정된다)
+pub fn true_peaks(samples: &[f32]) -> Vec<f32> {
+    let mut meter = TruePeakMeter::new();
+    let mut peaks = Vec::with_capacity(samples.len());
+
+    let padded = samples
+        .iter()
+        .copied()
+        .chain(std::iter::repeat_n(0.0, TRUE_PEAK_DELAY));
+    for (i, sample) in padded.enumerate() {
+        let peak = meter.process(sample);
+        if i >= TRUE_PEAK_DELAY {
+            peaks.push(peak.max(samples[i - TRUE_PEAK_DELAY].abs()));
+        }
+    }
+    peaks
+}
+
+fn energy_to_lufs(energy: f64) -> f64 {
+    if energy <= 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        LOUDNESS_OFFSET + 10.0 * energy.log10()
+    }
+}
+
+fn lufs_to_energy(lufs: f64) -> f64 {
+    10.0_f64.powf((lufs - LOUDNESS_OFFSET) / 10.0)
+}
+
+// 절대/상대 게이트를 통과한 블록 에너지의 평균으로 통합 라우드니스를 구한다.
+fn gated_loudness(block_energies: &[f64]) -> f64 {
+    let absolute_gate = lufs_to_energy(ABSOLUTE_GATE_LUFS);
+    let above_absolute: Vec<f64> = block_energies
+        .iter()
+        .copied()
+        .filter(|&energy| energy > absolute_gate)
+        .collect();
+    if above_absolute.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+
+    let ungated = above_absolute.iter().sum::<f64>() / above_absolute.len() as f64;
+    let relative_gate = lufs_to_energy(energy_to_lufs(ungated) + INTEGRATED_RELATIVE_GATE_LU);
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|&energy| energy > relative_gate)
+        .collect();
+    if gated.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+
+    energy_to_lufs(gated.iter().sum::<f64>() / gated.len() as f64)
+}
+
+// short-term 라우드니스 분포의 10% ~ 95% 범위 (EBU Tech 3342)
+fn loudness_range(short_term_energies: &[f64]) -> f64 {
+    let absolute_gate = lufs_to_energy(ABSOLUTE_GATE_LUFS);
+    let above_absolute: Vec<f64> = short_term_energies
+        .iter()
+        .copied()
+        .filter(|&energy| energy > absolute_gate)
+        .collect();
+    if above_absolute.is_empty() {
+        return 0.0;
+    }
+
+    let mean = above_absolute.iter().sum::<f64>() / above_absolute.len() as f64;
+    let relative_gate = energy_to_lufs(mean) + RANGE_RELATIVE_GATE_LU;
+    let mut levels: Vec<f64> = above_absolute
+        .into_iter()
+        .map(energy_to_lufs)
+        .filter(|&lufs| lufs > relative_gate)
+        .collect();
+    if levels.is_empty() {
+        return 0.0;
+    }
+
+    levels.sort_by(|a, b| a.total_cmp(b));
+    let percentile = |p: f64| levels[((levels.len() - 1) as f64 * p).round() as usize];
+    percentile(0.95) - percentile(0.10)
+}
+
+/// 프레임 단위로 샘플을 받아 EBU R128 라우드니스를 측정한다.
+/// 채널 배치 정보가 없으므로 모든 채널에 같은 가중치를 적용한다.
+pub struct LoudnessMeter {
+    filters: Vec<KWeighting>,
+    true_peaks: Vec<TruePeakMeter>,
+    sub_block_size: usize,
+    sub_block_frames: usize,
+    sub_block_energy: f64,
+    sub_blocks: Vec<f64>,
+    sample_peak: f32,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        Self {
+            filters: vec![KWeighting::new(sample_rate); channels.max(1)],
+            true_peaks: vec![TruePeakMeter::new(); channels.max(1)],
+            sub_block_size: ((sample_rate * SUB_BLOCK_MS / 1000) as usize).max(1),
+            sub_block_frames: 0,
+            sub_block_energy: 0.0,
+            sub_blocks: Vec::new(),
+            sample_peak: 0.0,
+        }
+    }
+
+    /// 채널 수만큼의 샘플로 이루어진 한 프레임을 처리한다.
+    pub fn process_frame(&mut self, frame: &[f32]) {
+        for ((filter, true_peak), &sample) in self
+            .filters
+            .iter_mut()
+            .zip(self.true_peaks.iter_mut())
+            .zip(frame)
+        {
+            let weighted = filter.process(sample);
+            self.sub_block_energy += weighted * weighted;
+            self.sample_peak = self.sample_peak.max(sample.abs());
+            true_peak.process(sample);
+        }
+
+        self.sub_block_frames += 1;
+        if self.sub_block_frames == self.sub_block_size {
+            self.sub_blocks
+                .push(self.sub_block_energy / self.sub_block_size as f64);
+            self.sub_block_frames = 0;
+            self.sub_block_energy = 0.0;
+        }
+    }
+
+    /// 채널이 교차 배치된 샘플을 처리한다.
+    pub fn process_interleaved(&mut self, samples: &[f32]) {
+        let channels = self.filters.len();
+        for frame in samples.chunks_exact(channels) {
+            self.process_frame(frame);
+        }
+    }
+
+    // 100ms 블록을 length개씩 묶어 100ms 간격으로 이동하며 평균 에너지를 구한다.
+    fn sliding_energies(&self, length: usize) -> Vec<f64> {
+        self.sub_blocks
+            .windows(length)
+            .map(|blocks| blocks.iter().sum::<f64>() / length as f64)
+            .collect()
+    }
+
+    pub fn report(&self) -> LoudnessReport {
+        // 마지막 샘플들이 필터 지연 때문에 아직 반영되지 않았으므로 0을 넣어 밀어낸다.
+        let true_peak = self
+            .true_peaks
+            .iter()
+            .map(|meter| {
+                let mut meter = *meter;
+                (0..TRUE_PEAK_DELAY).for_each(|_| {
+                    meter.process(0.0);
+                });
+                meter.peak()
+            })
+            .fold(self.sample_peak, f32::max);
+        let momentary = self.sliding_energies(MOMENTARY_SUB_BLOCKS);
+        let short_term = self.sliding_energies(SHORT_TERM_SUB_BLOCKS);
+        let max_lufs = |energies: &[f64]| {
+            energies
+                .iter()
+                .copied()
+                .fold(f64::NEG_INFINITY, |max, energy| {
+                    max.max(energy_to_lufs(energy))
+                })
+        };
+
+        LoudnessReport {
+            integrated_lufs: gated_loudness(&momentary) as f32,
+            max_momentary_lufs: max_lufs(&momentary) as f32,
+            max_short_term_lufs: max_lufs(&short_term) as f32,
+            loudness_range_lu: loudness_range(&short_term) as f32,
+            sample_peak_db: 20.0 * self.sample_peak.log10(),
+            true_peak_db: 20.0 * true_peak.log10(),
+        }
+    }
+}
+
+/// 채널이 교차 배치된 샘플의 라우드니스를 측정한다.
+pub fn measure(sample_rate: u32, channels: usize, samples: &[f32]) -> LoudnessReport {
+    let mut meter = LoudnessMeter::new(sample_rate, channels);
+    meter.process_interleaved(samples);
+    meter.report()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sine(sample_rate: u32, frequency: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(sample_rate as f32 * seconds) as usize)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (t * frequency * std::f32::consts::TAU).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_full_scale_1khz_sine_is_minus_3_lufs() {
+        // BS.1770: 0 dBFS 1kHz 사인파(모노)는 약 -3.01 LUFS
+        let report = measure(48000, 1, &sine(48000, 1000.0, 1.0, 5.0));
+
+        assert!((report.integrated_lufs + 3.01).abs() < 0.1);
+        assert!((report.max_momentary_lufs + 3.01).abs() < 0.1);
+        assert!(report.loudness_range_lu.abs() < 0.1);
+    }
+
+    #[test]
+    fn test_relative_gate_ignores_quiet_parts() {
+        let mut samples = sine(44100, 1000.0, 0.5, 10.0);
+        samples.extend(sine(44100, 1000.0, 0.005, 10.0));
+        let report = measure(44100, 1, &samples);
+
+        // 40dB 작은 부분은 상대 게이트로 제외되어 큰 부분만의 라우드니스와 같아야 한다.
+        assert!((report.integrated_lufs - (-3.01 - 6.02)).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_true_peak_finds_inter_sample_peak() {
+        // fs/4 사인파를 45도 위상으로 샘플링하면 샘플 값은 ±0.707이지만 실제 피크는 1.0이다.
+        let samples: Vec<f32> = (0..48000)
+            .map(|i| (i as f32 * std::f32::consts::FRAC_PI_2 + std::f32::consts::FRAC_PI_4).sin())
+            .collect();
+        let report = measure(48000, 1, &samples);
+
+        assert!((report.sample_peak_db + 3.01).abs() < 0.1);
+        assert!(report.true_peak_db.abs() < 0.5);
+    }
+}
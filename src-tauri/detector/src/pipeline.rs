@@ -0,0 +1,431 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::analyzer::{
+    create_wav, finalize_wav, legacy_threshold_to_dbfs, write_samples, AudioAnalyzerError,
+    AudioAnalyzerOption, AudioNormalizerOption, NormalizationMeter, NormalizationMode,
+    NormalizationReport, Progress, Region, Segment, SegmentStream,
+};
+use super::cancel::CancellationToken;
+use super::converter::{
+    estimate_stream_frames, stream_video_audio, ConverterError, STREAM_SAMPLE_RATE,
+};
+#[cfg(feature = "deepfilter")]
+use super::deepfilter::{denoise_samples, DeepFilterNetError, Preset, SampleStream};
+use super::limiter::{Limiter, LimiterOption};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PipelineError {
+    #[error(transparent)]
+    Converter(#[from] ConverterError),
+    #[cfg(feature = "deepfilter")]
+    #[error(transparent)]
+    DeepFilterNet(#[from] DeepFilterNetError),
+    #[error(transparent)]
+    Analyzer(#[from] AudioAnalyzerError),
+}
+
+/// 비디오 하나를 분석하는 설정
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisSetting {
+    #[cfg(feature = "deepfilter")]
+    pub use_deepfilternet: bool,
+    #[cfg(feature = "deepfilter")]
+    pub deepfilter_preset: Preset,
+    /// 잡음만 제거한 오디오를 결과물로 따로 남긴다.
+    #[cfg(feature = "deepfilter")]
+    pub export_denoised: bool,
+    /// 비디오에서 추출한 오디오를 파일로 남긴다.
+    pub export_raw: bool,
+    /// 잡음 제거와 정규화를 거친 분석용 오디오를 파일로 남긴다.
+    pub export_analysis: bool,
+    pub use_normalize: bool,
+    pub target_db: f32,
+    pub normalization_mode: NormalizationMode,
+    /// None이면 리미터 없이 ±1.0을 넘는 샘플을 잘라낸다.
+    pub limiter: Option<LimiterOption>,
+    /// analyzer.threshold_db와 close_threshold_db가 이전 버전(16비트 정수 스케일)의 값이다.
+    pub legacy_threshold: bool,
+    pub analyzer: AudioAnalyzerOption,
+}
+
+impl Default for AnalysisSetting {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "deepfilter")]
+            use_deepfilternet: false,
+            #[cfg(feature = "deepfilter")]
+            deepfilter_preset: Preset::default(),
+            #[cfg(feature = "deepfilter")]
+            export_denoised: false,
+            export_raw: false,
+            export_analysis: false,
+            use_normalize: false,
+            target_db: -3.0,
+            normalization_mode: NormalizationMode::default(),
+            limiter: Some(LimiterOption::default()),
+            legacy_threshold: false,
+            analyzer: AudioAnalyzerOption::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisResult {
+    pub segments: Vec<Segment>,
+    pub timeline: Vec<Region>,
+    pub duration: f32,
+    pub threshold_db: f32,
+    pub noise_floor_db: Option<f32>,
+    pub loudness: Option<NormalizationReport>,
+    /// 분석에 사용한 오디오 (artifacts.analysis_path와 같다)
+    pub output_path: Option<String>,
+    pub artifacts: AudioArtifacts,
+}
+
+/// 분석 과정에서 남긴 오디오 파일. 설정에서 요청한 것만 만든다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioArtifacts {
+    /// 비디오에서 추출한 원본 오디오 (export_raw가 켜져 있을 때)
+    pub raw_path: Option<String>,
+    /// 잡음 제거와 정규화를 거친 분석용 오디오 (export_analysis가 켜져 있을 때)
+    pub analysis_path: Option<String>,
+    /// 잡음만 제거한 결과물 (export_denoised가 켜져 있을 때)
+    pub denoised_path: Option<String>,
+}
+
+// 파일로 남기는 오디오 형식. 비디오에서 추출할 때와 같은 48kHz 모노 16비트
+const EXPORT_SPEC: hound::WavSpec = hound::WavSpec {
+    channels: 1,
+    sample_rate: STREAM_SAMPLE_RATE,
+    bits_per_sample: 16,
+    sample_format: hound::SampleFormat::Int,
+};
+
+// 정규화 게인을 적용하기 전의 분석용 오디오를 잠시 저장하는 형식
+const UNNORMALIZED_SPEC: hound::WavSpec = hound::WavSpec {
+    channels: 1,
+    sample_rate: STREAM_SAMPLE_RATE,
+    bits_per_sample: 32,
+    sample_format: hound::SampleFormat::Float,
+};
+
+// 게인을 적용해 다시 쓸 때 한 번에 쓰는 샘플 수
+const REWRITE_BLOCK: usize = 4096;
+
+/// 비디오의 오디오를 한 번만 디코딩해 블록 단위로 잡음 제거, 정규화 측정, 소리 구간 검출에 흘려 보낸다.
+/// 샘플은 블록 단위로만 다루고, 설정에서 요청한 것만 블록이 지나갈 때마다 work_dir의 WAV에 쓴다.
+/// 정규화 게인은 끝까지 읽어야 정해지므로 검출에는 윈도우 레벨에 반영한다.
+/// model_path는 `deepfilter` 기능이 꺼져 있으면 쓰이지 않는다.
+pub fn run_analysis<P>(
+    video_path: &str,
+    setting: AnalysisSetting,
+    model_path: &str,
+    work_dir: &Path,
+    cancel: &CancellationToken,
+    emit_progress: P,
+) -> Result<AnalysisResult, PipelineError>
+where
+    P: Fn(Progress) + Clone + Send + Sync + 'static,
+{
+    let filename = Path::new(video_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("temp");
+    let export_path = |suffix: &str| {
+        work_dir
+            .join(format!("{}{}.wav", filename, suffix))
+            .to_string_lossy()
+            .to_string()
+    };
+
+    #[cfg(feature = "deepfilter")]
+    let (denoise, export_denoised) = (
+        setting.use_deepfilternet,
+        setting.use_deepfilternet && setting.export_denoised,
+    );
+    #[cfg(not(feature = "deepfilter"))]
+    let (denoise, export_denoised, _) = (false, false, model_path);
+
+    // 이전 버전(16비트 정수 스케일)의 임계값은 dBFS로 변환
+    let mut option = setting.analyzer;
+    if setting.legacy_threshold {
+        option.threshold_db = legacy_threshold_to_dbfs(option.threshold_db);
+        option.close_threshold_db = option.close_threshold_db.map(legacy_threshold_to_dbfs);
+    }
+    let normalizer_option = AudioNormalizerOption {
+        target_db: setting.target_db,
+        mode: setting.normalization_mode,
+        limiter: setting.limiter,
+        ..Default::default()
+    };
+
+    let raw_path = setting.export_raw.then(|| export_path(""));
+    let denoised_path = export_denoised.then(|| export_path(".denoised"));
+    let analysis_path = setting.export_analysis.then(|| export_path(".analysis"));
+    // 정규화할 때는 게인을 적용하기 전의 분석용 오디오를 먼저 저장해 두고 나중에 게인을 적용해 다시 쓴다.
+    let unnormalized_path = analysis_path
+        .as_ref()
+        .filter(|_| setting.use_normalize)
+        .map(|_| export_path(".analysis.unnormalized"));
+
+    let mut raw_writer = match &raw_path {
+        Some(path) => Some(create_wav(path, EXPORT_SPEC)?),
+        None => None,
+    };
+    let mut denoised_writer = match &denoised_path {
+        Some(path) => Some(create_wav(path, EXPORT_SPEC)?),
+        None => None,
+    };
+    let mut analysis_writer = match (&unnormalized_path, &analysis_path) {
+        (Some(path), _) => Some(create_wav(path, UNNORMALIZED_SPEC)?),
+        (None, Some(path)) => Some(create_wav(path, EXPORT_SPEC)?),
+        _ => None,
+    };
+
+    let mut normalizer = setting
+        .use_normalize
+        .then(|| NormalizationMeter::new(STREAM_SAMPLE_RATE, 1, &normalizer_option));
+    let mut detector = option.detector.detector();
+    let mut segments = SegmentStream::new(STREAM_SAMPLE_RATE, option, detector.as_mut());
+
+    // 잡음 제거까지 끝난 블록을 파일로 남기고 정규화 측정과 구간 검출에 넘긴다.
+    let mut analyze = |block: &[f32]| -> Result<(), AudioAnalyzerError> {
+        if let Some(writer) = denoised_writer.as_mut() {
+            write_samples(writer, block)?;
+        }
+        if let Some(writer) = analysis_writer.as_mut() {
+            write_samples(writer, block)?;
+        }
+        if let Some(normalizer) = normalizer.as_mut() {
+            normalizer.push(block);
+        }
+        segments.push(block);
+        Ok(())
+    };
+
+    let frames = estimate_stream_frames(video_path);
+    stream_video_audio(video_path, cancel, |blocks| {
+        let mut error = None;
+        let blocks = blocks.map_while(|block| {
+            let block = block.map_err(PipelineError::from).and_then(|block| {
+                if let Some(writer) = raw_writer.as_mut() {
+                    write_samples(writer, &block)?;
+                }
+                Ok(block)
+            });
+            match block {
+                Ok(block) => Some(block),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        });
+
+        #[cfg(feature = "deepfilter")]
+        let result = if denoise {
+            denoise_blocks(
+                blocks,
+                frames,
+                setting.deepfilter_preset,
+                model_path,
+                cancel,
+                emit_progress.clone(),
+                &mut analyze,
+            )
+        } else {
+            analyze_blocks(blocks, frames, &emit_progress, &mut analyze)
+        };
+        #[cfg(not(feature = "deepfilter"))]
+        let result = analyze_blocks(blocks, frames, &emit_progress, &mut analyze);
+
+        // 디코딩이 도중에 끝났다면 (취소나 변환 오류) 그 오류를 먼저 알린다.
+        match error {
+            Some(e) => Err(e),
+            None => result,
+        }
+    })?;
+
+    for writer in [raw_writer, denoised_writer, analysis_writer]
+        .into_iter()
+        .flatten()
+    {
+        finalize_wav(writer)?;
+    }
+
+    let loudness = normalizer.map(|normalizer| normalizer.report());
+    if let (Some(source), Some(path), Some(report)) =
+        (&unnormalized_path, &analysis_path, &loudness)
+    {
+        emit_progress(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 0.0,
+        });
+        rewrite_with_gain(source, path, report.gain_db, &normalizer_option)?;
+        std::fs::remove_file(source).map_err(|e| AudioAnalyzerError::WriteError(e.to_string()))?;
+        emit_progress(Progress {
+            phase: "Normalizing Audio".to_string(),
+            percentage: 100.0,
+        });
+    }
+
+    // 소리가 있는 구간 감지
+    let result = match &loudness {
+        Some(report) => segments.finish(
+            report.gain_db,
+            Some(normalizer_option.ceiling_db()),
+            cancel,
+            &mut emit_progress.clone(),
+        ),
+        None => segments.finish(0.0, None, cancel, &mut emit_progress.clone()),
+    }?;
+
+    Ok(AnalysisResult {
+        segments: result.segments,
+        timeline: result.timeline,
+        duration: result.duration,
+        threshold_db: result.threshold_db,
+        noise_floor_db: result.noise_floor_db,
+        loudness,
+        output_path: analysis_path.clone(),
+        artifacts: AudioArtifacts {
+            raw_path,
+            analysis_path,
+            denoised_path,
+        },
+    })
+}
+
+// 게인을 적용하기 전에 저장해 둔 분석용 오디오를 읽어 정규화 게인과 리미터를 적용해 다시 쓴다.
+fn rewrite_with_gain(
+    source: &str,
+    path: &str,
+    gain_db: f32,
+    option: &AudioNormalizerOption,
+) -> Result<(), AudioAnalyzerError> {
+    let mut reader =
+        hound::WavReader::open(source).map_err(|e| AudioAnalyzerError::ReadError(e.to_string()))?;
+    let mut writer = create_wav(path, EXPORT_SPEC)?;
+    let gain = 10.0_f32.powf(gain_db / 20.0);
+    let mut limiter = option
+        .limiter
+        .map(|limiter| Limiter::new(1, STREAM_SAMPLE_RATE, limiter));
+
+    // 리미터를 거친 샘플은 잘라낸 뒤 블록 단위로 쓴다.
+    let mut block = Vec::with_capacity(REWRITE_BLOCK);
+    let mut write = |block: &mut Vec<f32>, sample: f32| -> Result<(), AudioAnalyzerError> {
+        block.push(sample.clamp(-1.0, 1.0));
+        if block.len() == REWRITE_BLOCK {
+            write_samples(&mut writer, block)?;
+            block.clear();
+        }
+        Ok(())
+    };
+
+    for sample in reader.samples::<f32>() {
+        let sample = sample.map_err(|e| AudioAnalyzerError::ReadError(e.to_string()))? * gain;
+        match limiter.as_mut() {
+            Some(limiter) => {
+                if let Some(frame) = limiter.process(&[sample]) {
+                    write(&mut block, frame[0])?;
+                }
+            }
+            None => write(&mut block, sample)?,
+        }
+    }
+    if let Some(limiter) = limiter.as_mut() {
+        while let Some(frame) = limiter.flush() {
+            write(&mut block, frame[0])?;
+        }
+    }
+
+    write_samples(&mut writer, &block)?;
+    finalize_wav(writer)
+}
+
+// 디코딩한 블록을 그대로 analyze에 넘긴다. 길이를 어림할 수 있으면 1% 단위로 진행률을 알린다.
+fn analyze_blocks<I, P, A>(
+    blocks: I,
+    frames: Option<usize>,
+    emit_progress: &P,
+    mut analyze: A,
+) -> Result<(), PipelineError>
+where
+    I: Iterator<Item = Vec<f32>>,
+    P: Fn(Progress),
+    A: FnMut(&[f32]) -> Result<(), AudioAnalyzerError>,
+{
+    let progress = |percentage: f32| {
+        emit_progress(Progress {
+            phase: "Converting Video to Audio".to_string(),
+            percentage,
+        })
+    };
+
+    progress(0.0);
+    let mut processed = 0;
+    let mut reported = 0;
+    for block in blocks {
+        analyze(&block)?;
+        processed += block.len();
+        if let Some(frames) = frames.filter(|&frames| frames > 0) {
+            let percent = (processed * 100 / frames).min(99);
+            if percent > reported {
+                reported = percent;
+                progress(percent as f32);
+            }
+        }
+    }
+    progress(100.0);
+    Ok(())
+}
+
+// 디코딩한 블록을 바로 DeepFilterNet에 흘려 보내고 잡음을 제거한 블록을 analyze에 넘긴다.
+// analyze가 실패하면 남은 블록은 넘기지 않고 그 오류를 돌려준다.
+#[cfg(feature = "deepfilter")]
+fn denoise_blocks<I, P, A>(
+    blocks: I,
+    frames: Option<usize>,
+    preset: Preset,
+    model_path: &str,
+    cancel: &CancellationToken,
+    emit_progress: P,
+    mut analyze: A,
+) -> Result<(), PipelineError>
+where
+    I: Iterator<Item = Vec<f32>>,
+    P: Fn(Progress),
+    A: FnMut(&[f32]) -> Result<(), AudioAnalyzerError>,
+{
+    let params = preset.parameter()?;
+    let mut failed = None;
+    denoise_samples(
+        params,
+        model_path,
+        SampleStream {
+            sample_rate: STREAM_SAMPLE_RATE,
+            channels: 1,
+            frames,
+            blocks,
+        },
+        |block| {
+            if failed.is_none() {
+                failed = analyze(block).err();
+            }
+        },
+        cancel,
+        |progress| {
+            emit_progress(Progress {
+                phase: "Applying DeepFilterNet".to_string(),
+                percentage: progress,
+            })
+        },
+    )?;
+    match failed {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
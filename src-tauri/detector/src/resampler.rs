@@ -0,0 +1,154 @@
+use std::f64::consts::PI;
+
+// 업샘플링 기준 윈도우 sinc 보간의 한쪽 탭 수
+const HALF_TAPS: usize = 16;
+
+/// 한 채널을 블록 단위로 나눠 받아 리샘플링하는 윈도우 sinc 리샘플러.
+/// 출력은 입력과 같은 시점에 정렬되며(지연 없음), 전체 출력 길이는
+/// ceil(입력 길이 * to_rate / from_rate)이다.
+pub struct Resampler {
+    from_rate: usize,
+    to_rate: usize,
+    // 출력 샘플 하나당 입력 샘플 수
+    step: f64,
+    // 다운샘플링할 때 에일리어싱을 막기 위한 정규화 차단 주파수 (1.0이면 입력 나이퀴스트)
+    cutoff: f64,
+    // 출력 샘플 하나에 쓰이는 한쪽 입력 샘플 수
+    width: i64,
+    // 아직 필요한 입력 샘플. history[0]의 입력 인덱스는 offset이다.
+    history: Vec<f32>,
+    offset: usize,
+    received: usize,
+    produced: usize,
+}
+
+impl Resampler {
+    pub fn new(from_rate: usize, to_rate: usize) -> Self {
+        let from_rate = from_rate.max(1);
+        let to_rate = to_rate.max(1);
+        let cutoff = (to_rate as f64 / from_rate as f64).min(1.0);
+        Self {
+            from_rate,
+            to_rate,
+            step: from_rate as f64 / to_rate as f64,
+            cutoff,
+            width: (HALF_TAPS as f64 / cutoff).ceil() as i64,
+            history: Vec::new(),
+            offset: 0,
+            received: 0,
+            produced: 0,
+        }
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.from_rate == self.to_rate
+    }
+
+    // 입력 시점 t의 값을 보간한다. 범위를 벗어난 입력은 0으로 본다.
+    fn interpolate(&self, t: f64) -> f32 {
+        let center = t.floor() as i64;
+        let mut value = 0.0;
+        let mut weight_sum = 0.0;
+
+        for k in (center - self.width + 1)..=(center + self.width) {
+            let distance = t - k as f64;
+            let x = self.cutoff * distance;
+            let sinc = if x == 0.0 {
+                1.0
+            } else {
+                (PI * x).sin() / (PI * x)
+            };
+            let window = 0.5 * (1.0 + (PI * distance / self.width as f64).cos());
+            let weight = sinc * window;
+            weight_sum += weight;
+
+            if k >= 0 && (k as usize) < self.received {
+                value += weight * self.history[k as usize - self.offset] as f64;
+            }
+        }
+
+        // 탭 합으로 나눠 DC 게인을 1로 맞춘다.
+        if weight_sum == 0.0 {
+            0.0
+        } else {
+            (value / weight_sum) as f32
+        }
+    }
+
+    /// 입력 블록을 넣고, 만들 수 있는 만큼의 출력을 output에 덧붙인다.
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if self.is_passthrough() {
+            output.extend_from_slice(input);
+            return;
+        }
+
+        self.history.extend_from_slice(input);
+        self.received += input.len();
+
+        loop {
+            let t = self.produced as f64 * self.step;
+            if t.floor() as i64 + self.width >= self.received as i64 {
+                break;
+            }
+            output.push(self.interpolate(t));
+            self.produced += 1;
+        }
+
+        // 다음 출력에 더 이상 쓰이지 않는 입력은 버린다.
+        let next = (self.produced as f64 * self.step).floor() as i64;
+        let first_needed = (next - self.width + 1).max(0) as usize;
+        if first_needed > self.offset {
+            let drop = (first_needed - self.offset).min(self.history.len());
+            self.history.drain(..drop);
+            self.offset += drop;
+        }
+    }
+
+    /// 입력이 끝났을 때 남은 출력을 모두 덧붙인다.
+    pub fn flush(&mut self, output: &mut Vec<f32>) {
+        if self.is_passthrough() {
+            return;
+        }
+
+        let total = (self.received * self.to_rate).div_ceil(self.from_rate);
+        while self.produced < total {
+            output.push(self.interpolate(self.produced as f64 * self.step));
+            self.produced += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sine(sample_rate: usize, frequency: f64, length: usize) -> Vec<f32> {
+        (0..length)
+            .map(|i| (2.0 * PI * frequency * i as f64 / sample_rate as f64).sin() as f32)
+            .collect()
+    }
+
+    #[test]
+    fn test_resampler_matches_signal_in_blocks() {
+        for (from_rate, to_rate) in [(44100, 48000), (48000, 16000)] {
+            let input = sine(from_rate, 440.0, from_rate);
+            let mut resampler = Resampler::new(from_rate, to_rate);
+            let mut output = Vec::new();
+            // 블록 크기와 관계없이 같은 결과가 나와야 한다.
+            for block in input.chunks(1000) {
+                resampler.process(block, &mut output);
+            }
+            resampler.flush(&mut output);
+
+            assert_eq!(output.len(), to_rate);
+            let expected = sine(to_rate, 440.0, to_rate);
+            // 양 끝은 0으로 채운 입력의 영향을 받으므로 가운데만 비교한다.
+            for (actual, expected) in output[100..to_rate - 100]
+                .iter()
+                .zip(&expected[100..to_rate - 100])
+            {
+                assert!((actual - expected).abs() < 1e-2);
+            }
+        }
+    }
+}
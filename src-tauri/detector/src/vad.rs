@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+
+use super::analyzer::{
+    close_ratio, resolve_open_amplitudes, AnalysisWindow, AudioAnalyzerOption, SegmentDetector,
+    WindowActivity, WindowBuffer, WindowLevels,
+};
+use super::filter::{Biquad, BUTTERWORTH_Q};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VadOption {
+    /// 음성 대역 하한 (Hz)
+    pub low_hz: f32,
+    /// 음성 대역 상한 (Hz)
+    pub high_hz: f32,
+    /// 윈도우 에너지 중 음성 대역이 차지해야 하는 최소 비율 (0 ~ 1)
+    pub min_band_ratio: f32,
+    /// 영교차율로 추정한 주 주파수의 허용 범위 (Hz)
+    pub min_zero_crossing_hz: f32,
+    pub max_zero_crossing_hz: f32,
+}
+
+// 하한을 전화 대역(300Hz)보다 낮춰 낮은 목소리의 /i/, /u/처럼 첫 포먼트가 300Hz 근처인 모음도 통과시킨다.
+// 60Hz 험과 그 배음은 이 하한에서도 음성 대역 비율이 0.15 정도에 그친다.
+impl Default for VadOption {
+    fn default() -> Self {
+        Self {
+            low_hz: 150.0,
+            high_hz: 3400.0,
+            min_band_ratio: 0.25,
+            min_zero_crossing_hz: 100.0,
+            max_zero_crossing_hz: 3000.0,
+        }
+    }
+}
+
+impl VadOption {
+    // (샘플, 음성 대역 샘플) 쌍으로 이루어진 윈도우가 음성 특징을 가졌는지 판정한다.
+    fn is_speech(&self, chunk: &[(f32, f32)], sample_rate: u32) -> bool {
+        let total_energy: f32 = chunk.iter().map(|&(s, _)| s * s).sum();
+        let band_energy: f32 = chunk.iter().map(|&(_, b)| b * b).sum();
+        let crossings = chunk
+            .windows(2)
+            .filter(|pair| (pair[0].0 >= 0.0) != (pair[1].0 >= 0.0))
+            .count();
+
+        let band_ratio = if total_energy > 0.0 {
+            band_energy / total_energy
+        } else {
+            0.0
+        };
+        // 초당 영교차 횟수의 절반이 주 주파수에 해당한다.
+        let zero_crossing_hz = crossings as f32 / 2.0 * sample_rate as f32 / chunk.len() as f32;
+
+        band_ratio >= self.min_band_ratio
+            && zero_crossing_hz >= self.min_zero_crossing_hz
+            && zero_crossing_hz <= self.max_zero_crossing_hz
+    }
+}
+
+// 분석 하나 동안 유지하는 음성 대역 필터와 윈도우
+struct VadState {
+    sample_rate: u32,
+    high_pass: Biquad,
+    low_pass: Biquad,
+    windows: WindowBuffer<(f32, f32)>,
+}
+
+/// RMS 임계값을 넘는 윈도우 중 음성 특징을 가진 윈도우만 소리 구간으로 판정한다.
+/// 험 노이즈처럼 음성 대역 밖에 에너지가 몰린 소리나, 숨소리처럼 영교차율이 높은 소리를 걸러낸다.
+/// 음성 특징은 레벨과 관계없으므로 블록을 받는 동안 윈도우마다 판정해 둔다.
+pub struct VoiceActivityDetector {
+    option: VadOption,
+    state: Option<VadState>,
+    speech: Vec<bool>,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(option: VadOption) -> Self {
+        Self {
+            option,
+            state: None,
+            speech: Vec::new(),
+        }
+    }
+}
+
+impl SegmentDetector for VoiceActivityDetector {
+    fn begin(&mut self, sample_rate: u32, window: AnalysisWindow) {
+        self.state = Some(VadState {
+            sample_rate,
+            high_pass: Biquad::high_pass(sample_rate, self.option.low_hz, BUTTERWORTH_Q),
+            low_pass: Biquad::low_pass(sample_rate, self.option.high_hz, BUTTERWORTH_Q),
+            windows: WindowBuffer::new(window),
+        });
+        self.speech.clear();
+    }
+
+    fn observe(&mut self, samples: &[f32]) {
+        let Self {
+            option,
+            state,
+            speech,
+        } = self;
+        if let Some(state) = state {
+            // 윈도우가 겹칠 수 있으므로 음성 대역 신호는 윈도우로 나누기 전에 이어서 계산한다.
+            let pairs: Vec<(f32, f32)> = samples
+                .iter()
+                .map(|&sample| {
+                    (
+                        sample,
+                        state.low_pass.process(state.high_pass.process(sample)),
+                    )
+                })
+                .collect();
+            let sample_rate = state.sample_rate;
+            state.windows.push(&pairs, |chunk| {
+                speech.push(option.is_speech(chunk, sample_rate))
+            });
+        }
+    }
+
+    fn finish(&mut self) {
+        let Self {
+            option,
+            state,
+            speech,
+        } = self;
+        if let Some(state) = state {
+            let sample_rate = state.sample_rate;
+            state
+                .windows
+                .finish(|chunk| speech.push(option.is_speech(chunk, sample_rate)));
+        }
+    }
+
+    fn classify(&self, levels: &WindowLevels, option: &AudioAnalyzerOption) -> WindowActivity {
+        let (threshold_db, noise_floor_db, open_amplitudes) =
+            resolve_open_amplitudes(&levels.rms, levels.sample_rate, levels.window, option);
+        let close_ratio = close_ratio(option);
+
+        let mut open = Vec::with_capacity(levels.rms.len());
+        let mut close = Vec::with_capacity(levels.rms.len());
+
+        for ((&rms, &open_amplitude), &is_speech) in
+            levels.rms.iter().zip(&open_amplitudes).zip(&self.speech)
+        {
+            open.push(is_speech && rms >= open_amplitude);
+            close.push(!is_speech || rms < open_amplitude * close_ratio);
+        }
+
+        WindowActivity {
+            open,
+            close,
+            threshold_db,
+            noise_floor_db,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analyzer::{AudioAnalyzer, DetectorKind, ProcessedAudio};
+    use crate::CancellationToken;
+
+    const SAMPLE_RATE: u32 = 16000;
+
+    // 모음별 (포먼트 주파수, 대역폭) (Hz)
+    const VOWEL_A: [(f32, f32); 3] = [(700.0, 130.0), (1220.0, 70.0), (2600.0, 160.0)];
+    const VOWEL_I: [(f32, f32); 3] = [(270.0, 60.0), (2290.0, 100.0), (3010.0, 120.0)];
+    const VOWEL_U: [(f32, f32); 3] = [(300.0, 60.0), (870.0, 80.0), (2240.0, 100.0)];
+
+    // 성문 펄스처럼 배음이 1/k로 줄어드는 신호에 모음의 포먼트 공진을 씌운다.
+    fn voiced(f0: f32, formants: [(f32, f32); 3], seconds: f32) -> Vec<f32> {
+        let harmonics: Vec<(f32, f32)> = (1..)
+            .map(|k| k as f32 * f0)
+            .take_while(|&frequency| frequency < 4000.0)
+            .enumerate()
+            .map(|(i, frequency)| {
+                let envelope: f32 = formants
+                    .iter()
+                    .map(|&(formant, bandwidth)| {
+                        let detune = (frequency - formant) / (bandwidth / 2.0);
+                        1.0 / (1.0 + detune * detune).sqrt()
+                    })
+                    .sum();
+                (frequency, envelope / (i + 1) as f32)
+            })
+            .collect();
+
+        let samples: Vec<f32> = (0..(seconds * SAMPLE_RATE as f32) as usize)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                harmonics
+                    .iter()
+                    .map(|&(frequency, amplitude)| {
+                        amplitude * (t * frequency * std::f32::consts::TAU).sin()
+                    })
+                    .sum()
+            })
+            .collect();
+        let peak = samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        samples.into_iter().map(|s| s / peak * 0.3).collect()
+    }
+
+    // 60Hz 전원 험과 그 배음
+    fn hum(seconds: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE as f32) as usize)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                [(60.0, 0.2), (120.0, 0.1), (180.0, 0.05)]
+                    .iter()
+                    .map(|&(frequency, amplitude)| {
+                        amplitude * (t * frequency * std::f32::consts::TAU).sin()
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn white_noise(seconds: f32) -> Vec<f32> {
+        let mut state = 0x1234_5678_u32;
+        (0..(seconds * SAMPLE_RATE as f32) as usize)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * 0.2
+            })
+            .collect()
+    }
+
+    fn detect(parts: &[Vec<f32>]) -> Vec<(f32, f32)> {
+        let audio = ProcessedAudio {
+            sample_rate: SAMPLE_RATE,
+            samples: parts.concat(),
+        };
+        let option = AudioAnalyzerOption {
+            detector: DetectorKind::Vad(VadOption::default()),
+            left_buffer_sec: 0.0,
+            right_buffer_sec: 0.0,
+            ..Default::default()
+        };
+        AudioAnalyzer::new()
+            .start_with_samples(audio, option, &CancellationToken::new(), |_| {})
+            .unwrap()
+            .segments
+            .iter()
+            .map(|segment| (segment.start, segment.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_voiced_speech() {
+        for (f0, formants) in [100.0, 140.0, 200.0]
+            .into_iter()
+            .flat_map(|f0| [VOWEL_A, VOWEL_I, VOWEL_U].map(|formants| (f0, formants)))
+        {
+            let segments = detect(&[vec![0.0; 8000], voiced(f0, formants, 1.0), vec![0.0; 8000]]);
+            assert_eq!(segments.len(), 1, "{} Hz: {:?}", f0, segments);
+            let (start, end) = segments[0];
+            assert!(
+                (start - 0.5).abs() < 0.03 && (end - 1.5).abs() < 0.03,
+                "{} Hz: {:?}",
+                f0,
+                segments
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_hum_and_noise() {
+        assert_eq!(detect(&[hum(2.0)]), vec![]);
+        assert_eq!(detect(&[white_noise(2.0)]), vec![]);
+
+        // 험 위의 음성은 음성 구간만 남는다.
+        let segments = detect(&[hum(0.5), voiced(140.0, VOWEL_A, 1.0), hum(0.5)]);
+        assert_eq!(segments.len(), 1, "{:?}", segments);
+    }
+}
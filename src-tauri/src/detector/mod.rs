@@ -1,4 +0,0 @@
-pub(crate) mod analyzer;
-pub(crate) mod converter;
-pub(crate) mod deepfilter;
-pub(crate) mod fcpxml;
@@ -1,18 +1,200 @@
-mod detector;
+use silent_cut_detector as detector;
 
-use detector::analyzer::{Progress, Segment};
+use detector::analyzer::{NormalizationMode, NormalizationReport, OutputFormat, Progress, Segment};
+use detector::cancel::CancellationToken;
 use detector::converter::VideoInfo;
-use detector::deepfilter::Parameter;
+use detector::deepfilter::Preset;
+use detector::limiter::LimiterOption;
+use detector::pipeline::{AnalysisResult, AnalysisSetting};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tauri::path::BaseDirectory;
 use tauri::{Emitter, Manager};
 
 #[derive(Debug, Serialize, Deserialize)]
-struct AnalysisResult {
-    segments: Vec<Segment>,
+struct NormalizationResult {
     output_path: String,
+    report: NormalizationReport,
+}
+
+// 취소 토큰을 따로 가지는 명령 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Operation {
+    Analysis,
+    Normalization,
+}
+
+// 진행 중인 작업을 취소하기 위한 상태. 명령 종류마다 토큰을 따로 두어
+// 정규화를 시작해도 진행 중인 분석의 토큰이 바뀌지 않게 한다. 작업을 시작할 때마다 새 토큰으로 바꾼다.
+#[derive(Default)]
+struct AnalysisState {
+    cancel: Mutex<HashMap<Operation, CancellationToken>>,
+}
+
+impl AnalysisState {
+    fn begin(&self, operation: Operation) -> CancellationToken {
+        let cancel = CancellationToken::new();
+        self.cancel
+            .lock()
+            .unwrap()
+            .insert(operation, cancel.clone());
+        cancel
+    }
+}
+
+// 배치 작업 관리자. 대기 중이거나 실행 중인 작업의 취소 토큰을 작업 id별로 가진다.
+#[derive(Default)]
+struct JobManager {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, CancellationToken>>,
+}
+
+/// 배치 작업이 끝난 뒤 파일마다 FCPXML을 만드는 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BatchExport {
+    fps: String,
+    resolution: String,
+    /// 없으면 비디오와 같은 폴더에 `<파일 이름>.fcpxml`로 저장한다.
+    output_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BatchJob {
+    job_id: u64,
+    video_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum JobStatus {
+    Queued,
+    Running,
+    Done {
+        result: AnalysisResult,
+        fcpxml_path: Option<String>,
+    },
+    Failed(String),
+    Cancelled,
+}
+
+// "batch-job" 이벤트로 보내는 작업 상태
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JobEvent {
+    job_id: u64,
+    status: JobStatus,
+}
+
+// "batch-progress" 이벤트로 보내는 작업별 진행률
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JobProgress {
+    job_id: u64,
+    phase: String,
+    percentage: f32,
+}
+
+// 정규화 방식이 지정되지 않으면 이전 버전의 peak_normalization 값을 따른다.
+fn resolve_normalization_mode(
+    mode: Option<NormalizationMode>,
+    peak_normalization: bool,
+) -> NormalizationMode {
+    mode.unwrap_or(if peak_normalization {
+        NormalizationMode::Peak
+    } else {
+        NormalizationMode::Rms
+    })
+}
+
+// DeepFilterNet을 쓰지 않으면 모델 경로가 필요 없으므로 빈 문자열을 돌려준다.
+fn deepfilter_model_path(
+    handle: &tauri::AppHandle,
+    setting: &AnalysisSetting,
+) -> Result<String, String> {
+    if !setting.use_deepfilternet {
+        return Ok(String::new());
+    }
+    let model_path = handle
+        .path()
+        .resolve("models/DeepFilterNet3_onnx.tar.gz", BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    Ok(model_path.to_string_lossy().to_string())
+}
+
+// 배치 작업 하나를 실행하고, 설정이 있으면 FCPXML까지 만든다.
+fn run_job(
+    job: &BatchJob,
+    setting: AnalysisSetting,
+    export: Option<&BatchExport>,
+    window: &tauri::Window,
+    cancel: &CancellationToken,
+) -> Result<JobStatus, String> {
+    // 오디오 파일은 FCPXML과 같은 폴더에 남긴다. 임시 폴더에 두면 FCPXML이 가리키는 파일이
+    // 사라지거나 다음 실행의 같은 작업 번호가 덮어쓴다.
+    let video_path = Path::new(&job.video_path);
+    let output_dir = export
+        .and_then(|export| export.output_dir.as_ref())
+        .map(PathBuf::from)
+        .or_else(|| video_path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    if setting.export_raw || setting.export_analysis || setting.export_denoised {
+        std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
+    }
+
+    let model_path = deepfilter_model_path(window.app_handle(), &setting)?;
+    let job_id = job.job_id;
+    let progress_window = window.clone();
+    let emit_progress = move |progress: Progress| {
+        progress_window
+            .emit(
+                "batch-progress",
+                JobProgress {
+                    job_id,
+                    phase: progress.phase,
+                    percentage: progress.percentage,
+                },
+            )
+            .unwrap();
+    };
+    let result = detector::pipeline::run_analysis(
+        &job.video_path,
+        setting,
+        &model_path,
+        &output_dir,
+        cancel,
+        emit_progress,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let fcpxml_path = match export {
+        Some(export) => {
+            let filename = video_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("output");
+            let output_path = output_dir
+                .join(format!("{}.fcpxml", filename))
+                .to_string_lossy()
+                .to_string();
+
+            let path = detector::fcpxml::generate_fcpxml(
+                detector::fcpxml::Setting::parse(&export.fps, &export.resolution)
+                    .map_err(|e| e.to_string())?,
+                &job.video_path,
+                result.segments.clone(),
+                result.artifacts.denoised_path.as_deref(),
+                &output_path,
+            )
+            .map_err(|e| e.to_string())?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    Ok(JobStatus::Done {
+        result,
+        fcpxml_path,
+    })
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -27,132 +209,150 @@ async fn get_video_info(video_path: String) -> Result<VideoInfo, String> {
     Ok(video_info)
 }
 
+// 비디오 하나를 분석한다. setting에서 빠진 값은 기본값을 쓴다.
 #[tauri::command]
 async fn analyze_video(
     video_path: String,
-    use_deepfilternet: bool,
-    use_normalize: bool,
-    target_db: f32,
-    peak_normalization: bool,
-    threshold_db: f32,
-    min_duration_ms: u32,
-    left_buffer_sec: f32,
-    right_buffer_sec: f32,
+    setting: Option<AnalysisSetting>,
     window: tauri::Window,
     handle: tauri::AppHandle,
+    state: tauri::State<'_, AnalysisState>,
 ) -> Result<AnalysisResult, String> {
+    let cancel = state.begin(Operation::Analysis);
+    let setting = setting.unwrap_or_default();
+    let model_path = deepfilter_model_path(&handle, &setting)?;
     let window = Arc::new(window);
-    // 임시 오디오 파일 경로 생성
-    let video_path_obj = Path::new(&video_path);
-    let filename = video_path_obj
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("temp");
-
-    let temp_dir = std::env::temp_dir();
-    let audio_path = temp_dir
-        .join(format!("{}.wav", filename))
-        .to_string_lossy()
-        .to_string();
-
-    // 비디오에서 오디오 추출
-    window
-        .emit(
-            "analyze-progress",
-            Progress {
-                phase: "Converting Video to Audio".to_string(),
-                percentage: 0.0,
-            },
-        )
-        .unwrap();
-    detector::converter::convert_video_to_audio(&video_path, &audio_path)
-        .map_err(|e| e.to_string())?;
-    window
-        .emit(
-            "analyze-progress",
-            Progress {
-                phase: "Converting Video to Audio".to_string(),
-                percentage: 100.0,
-            },
-        )
-        .unwrap();
 
-    if use_deepfilternet {
-        let model_path = handle
-            .path()
-            .resolve("models/DeepFilterNet3_onnx.tar.gz", BaseDirectory::Resource)
+    let emit_progress = move |progress: Progress| {
+        window.emit("analyze-progress", progress).unwrap();
+    };
+
+    detector::pipeline::run_analysis(
+        &video_path,
+        setting,
+        &model_path,
+        &std::env::temp_dir(),
+        &cancel,
+        emit_progress,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// 여러 비디오를 같은 설정으로 백그라운드에서 분석한다. 바로 작업 목록을 돌려주고,
+// 진행률은 "batch-progress", 상태 변화는 "batch-job" 이벤트로 작업 id와 함께 보낸다.
+#[tauri::command]
+fn start_batch(
+    video_paths: Vec<String>,
+    setting: Option<AnalysisSetting>,
+    concurrency: Option<usize>,
+    export: Option<BatchExport>,
+    window: tauri::Window,
+    jobs: tauri::State<'_, JobManager>,
+) -> Result<Vec<BatchJob>, String> {
+    let mut setting = setting.unwrap_or_default();
+    if let Some(export) = &export {
+        // 분석이 다 끝난 뒤에 실패하지 않도록 미리 확인한다.
+        detector::fcpxml::Setting::parse(&export.fps, &export.resolution)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut queue = VecDeque::new();
+    let mut batch = Vec::new();
+    for video_path in video_paths {
+        let job = BatchJob {
+            job_id: jobs.next_id.fetch_add(1, Ordering::Relaxed),
+            video_path,
+        };
+        let cancel = CancellationToken::new();
+        jobs.jobs.lock().unwrap().insert(job.job_id, cancel.clone());
+        window
+            .emit(
+                "batch-job",
+                JobEvent {
+                    job_id: job.job_id,
+                    status: JobStatus::Queued,
+                },
+            )
             .unwrap();
-        println!("model_path: {:?}", model_path);
-        let params = Parameter::default();
+        batch.push(job.clone());
+        queue.push_back((job, cancel));
+    }
+
+    let workers = concurrency.unwrap_or(1).clamp(1, queue.len().max(1));
+
+    // 작업마다 DeepFilterNet이 모든 코어를 쓰면 동시에 도는 작업끼리 경쟁하므로 코어를 나눠 준다.
+    if let Ok(mut parameter) = setting.deepfilter_preset.parameter() {
+        if parameter.workers == 0 {
+            let cores = std::thread::available_parallelism()
+                .map(|count| count.get())
+                .unwrap_or(1);
+            parameter.workers = (cores / workers).max(1);
+            setting.deepfilter_preset = Preset::Custom(parameter);
+        }
+    }
+
+    let queue = Arc::new(Mutex::new(queue));
+    let export = export.map(Arc::new);
+    for _ in 0..workers {
+        let queue = queue.clone();
+        let export = export.clone();
         let window = window.clone();
-        let progress_callback = move |progress: f32| {
+        std::thread::spawn(move || loop {
+            let Some((job, cancel)) = queue.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            let status = if cancel.is_cancelled() {
+                JobStatus::Cancelled
+            } else {
+                window
+                    .emit(
+                        "batch-job",
+                        JobEvent {
+                            job_id: job.job_id,
+                            status: JobStatus::Running,
+                        },
+                    )
+                    .unwrap();
+                match run_job(&job, setting, export.as_deref(), &window, &cancel) {
+                    Ok(status) => status,
+                    Err(_) if cancel.is_cancelled() => JobStatus::Cancelled,
+                    Err(e) => JobStatus::Failed(e),
+                }
+            };
+
+            window
+                .state::<JobManager>()
+                .jobs
+                .lock()
+                .unwrap()
+                .remove(&job.job_id);
             window
                 .emit(
-                    "analyze-progress",
-                    Progress {
-                        phase: "Applying DeepFilterNet".to_string(),
-                        percentage: progress,
+                    "batch-job",
+                    JobEvent {
+                        job_id: job.job_id,
+                        status,
                     },
                 )
                 .unwrap();
-        };
-        detector::deepfilter::apply_deepfilternet(
-            params,
-            &model_path.to_string_lossy(),
-            &audio_path,
-            &audio_path,
-            progress_callback,
-        )
-        .map_err(|e| e.to_string())?;
+        });
     }
 
-    if use_normalize {
-        let window = window.clone();
-        let analyzer = detector::analyzer::AudioAnalyzer::new();
-        let progress_callback = move |progress: Progress| {
-            window.emit("analyze-progress", progress).unwrap();
-        };
+    Ok(batch)
+}
 
-        // 오디오 정규화 실행
-        analyzer
-            .normalize(
-                &audio_path,
-                &audio_path,
-                detector::analyzer::AudioNormalizerOption {
-                    target_db,
-                    peak_normalization,
-                },
-                progress_callback,
-            )
-            .map_err(|e| e.to_string())?;
+// 배치 작업을 취소한다. job_ids가 없으면 남은 작업을 모두 취소한다.
+#[tauri::command]
+fn cancel_jobs(job_ids: Option<Vec<u64>>, jobs: tauri::State<'_, JobManager>) {
+    let jobs = jobs.jobs.lock().unwrap();
+    match job_ids {
+        Some(job_ids) => job_ids
+            .iter()
+            .filter_map(|job_id| jobs.get(job_id))
+            .for_each(CancellationToken::cancel),
+        None => jobs.values().for_each(CancellationToken::cancel),
     }
-
-    // 오디오 분석
-    let analyzer = detector::analyzer::AudioAnalyzer::new();
-
-    let progress_callback = move |progress: Progress| {
-        println!("progress: {:?}", progress);
-        window.emit("analyze-progress", progress).unwrap();
-    };
-
-    // 소리가 있는 구간 감지
-    let segments = analyzer
-        .start(
-            &audio_path,
-            detector::analyzer::AudioAnalyzerOption {
-                threshold_db,
-                min_duration_ms,
-                left_buffer_sec,
-                right_buffer_sec,
-            },
-            progress_callback,
-        )
-        .map_err(|e| e.to_string())?;
-    // 결과 반환
-    Ok(AnalysisResult {
-        segments,
-        output_path: audio_path,
-    })
 }
 
 #[tauri::command]
@@ -162,25 +362,10 @@ async fn generate_fcpxml(
     fps: String,
     resolution: String,
     output_path: String,
+    connected_audio_path: Option<String>,
 ) -> Result<String, String> {
     // FPS 및 해상도 설정
-    let fps = match fps.as_str() {
-        "29.97" => detector::fcpxml::FPS::FPS29_97,
-        "30" => detector::fcpxml::FPS::FPS30,
-        "59.94" => detector::fcpxml::FPS::FPS59_94,
-        "60" => detector::fcpxml::FPS::FPS60,
-        _ => return Err("지원하지 않는 FPS입니다".to_string()),
-    };
-
-    let resolution = match resolution.as_str() {
-        "SD" => detector::fcpxml::Resolution::SD,
-        "HD" => detector::fcpxml::Resolution::HD,
-        "FHD" => detector::fcpxml::Resolution::FHD,
-        "4K" => detector::fcpxml::Resolution::FourK,
-        _ => return Err("지원하지 않는 해상도입니다".to_string()),
-    };
-
-    let setting = detector::fcpxml::Setting { fps, resolution };
+    let setting = detector::fcpxml::Setting::parse(&fps, &resolution).map_err(|e| e.to_string())?;
 
     // 출력 파일 경로 설정
     let video_path_obj = Path::new(&video_path);
@@ -190,8 +375,14 @@ async fn generate_fcpxml(
         .unwrap_or("output");
 
     // FCPXML 생성
-    detector::fcpxml::generate_fcpxml(setting, &video_path, segments, &output_path)
-        .map_err(|e| e.to_string())
+    detector::fcpxml::generate_fcpxml(
+        setting,
+        &video_path,
+        segments,
+        connected_audio_path.as_deref(),
+        &output_path,
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -200,29 +391,53 @@ async fn normalize_audio(
     output_path: String,
     target_db: f32,
     peak_normalization: bool,
+    normalization_mode: Option<NormalizationMode>,
+    limiter: Option<LimiterOption>,
+    output_format: Option<OutputFormat>,
     window: tauri::Window,
-) -> Result<String, String> {
+    state: tauri::State<'_, AnalysisState>,
+) -> Result<NormalizationResult, String> {
+    let cancel = state.begin(Operation::Normalization);
     let analyzer = detector::analyzer::AudioAnalyzer::new();
 
     let progress_callback = move |progress: detector::analyzer::Progress| {
-        println!("progress: {:?}", progress);
         window.emit("analyze-progress", progress).unwrap();
     };
 
     // 오디오 정규화 실행
-    analyzer
+    let report = analyzer
         .normalize(
             &audio_path,
             &output_path,
             detector::analyzer::AudioNormalizerOption {
                 target_db,
-                peak_normalization,
+                mode: resolve_normalization_mode(normalization_mode, peak_normalization),
+                limiter,
+                output_format: output_format.unwrap_or_default(),
             },
+            &cancel,
             progress_callback,
         )
         .map_err(|e| e.to_string())?;
 
-    Ok(output_path)
+    Ok(NormalizationResult {
+        output_path,
+        report,
+    })
+}
+
+// 진행 중인 분석이나 정규화를 취소한다. operation이 없으면 둘 다 취소한다.
+// 취소된 명령은 "Cancelled" 오류를 돌려준다.
+#[tauri::command]
+fn cancel_analysis(operation: Option<Operation>, state: tauri::State<'_, AnalysisState>) {
+    let cancel = state.cancel.lock().unwrap();
+    match operation {
+        Some(operation) => cancel
+            .get(&operation)
+            .into_iter()
+            .for_each(CancellationToken::cancel),
+        None => cancel.values().for_each(CancellationToken::cancel),
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -231,12 +446,17 @@ pub fn run() {
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
+        .manage(AnalysisState::default())
+        .manage(JobManager::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             get_video_info,
             analyze_video,
             generate_fcpxml,
             normalize_audio,
+            cancel_analysis,
+            start_batch,
+            cancel_jobs,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
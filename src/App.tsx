@@ -54,6 +54,28 @@ import {
   Zap,
 } from "lucide-react";
 
+// 감지 설정은 localStorage에 저장한다.
+// 버전 1(또는 버전이 없는 값)은 threshold를 16비트 정수 스케일로 저장하던 이전 버전의 설정이다.
+const PRESET_STORAGE_KEY = "detectionPreset";
+const PRESET_VERSION = 2;
+
+interface DetectionPreset {
+  version?: number;
+  thresholdDb: number;
+  minDurationMs: number;
+  leftBufferSec: number;
+  rightBufferSec: number;
+}
+
+const loadPreset = (): DetectionPreset | null => {
+  try {
+    const stored = localStorage.getItem(PRESET_STORAGE_KEY);
+    return stored ? JSON.parse(stored) : null;
+  } catch {
+    return null;
+  }
+};
+
 function App() {
   const { t } = useTranslation();
   const [videoPath, setVideoPath] = useState<string>("");
@@ -77,10 +99,15 @@ function App() {
   const { getVideoInfo, analyzeVideo, generateFcpXml } = useCommand();
 
   // 설정 상태
-  const [thresholdDb, setThresholdDb] = useState<number>(10);
-  const [minDurationMs, setMinDurationMs] = useState<number>(100);
-  const [leftBufferSec, setLeftBufferSec] = useState<number>(0.01);
-  const [rightBufferSec, setRightBufferSec] = useState<number>(0.15);
+  const [preset] = useState<DetectionPreset | null>(loadPreset);
+  const [thresholdDb, setThresholdDb] = useState<number>(preset?.thresholdDb ?? -40);
+  // 이전 버전에서 저장한 threshold는 사용자가 바꾸기 전까지 분석할 때 dBFS로 변환한다.
+  const [legacyThreshold, setLegacyThreshold] = useState<boolean>(
+    preset !== null && (preset.version ?? 1) < PRESET_VERSION,
+  );
+  const [minDurationMs, setMinDurationMs] = useState<number>(preset?.minDurationMs ?? 100);
+  const [leftBufferSec, setLeftBufferSec] = useState<number>(preset?.leftBufferSec ?? 0.01);
+  const [rightBufferSec, setRightBufferSec] = useState<number>(preset?.rightBufferSec ?? 0.15);
   const [fps, setFps] = useState<string>("auto");
   const [resolution, setResolution] = useState<string>("auto");
 
@@ -88,6 +115,17 @@ function App() {
     getVersion().then((version) => setVersion(version));
   }, []);
 
+  useEffect(() => {
+    const preset: DetectionPreset = {
+      version: legacyThreshold ? 1 : PRESET_VERSION,
+      thresholdDb,
+      minDurationMs,
+      leftBufferSec,
+      rightBufferSec,
+    };
+    localStorage.setItem(PRESET_STORAGE_KEY, JSON.stringify(preset));
+  }, [legacyThreshold, thresholdDb, minDurationMs, leftBufferSec, rightBufferSec]);
+
   // 진행률 이벤트 리스너 설정
   useEffect(() => {
     let unlisten: (() => void) | undefined;
@@ -174,17 +212,19 @@ function App() {
     setProgress({ phase: "", percentage: 0 });
 
     try {
-      const result: AnalysisResult = await analyzeVideo(
-        videoPath,
-        useDeepfilternet,
-        thresholdDb,
-        minDurationMs,
-        leftBufferSec,
-        rightBufferSec,
-        useNormalize,
-        peakNormalization,
-        targetDb
-      );
+      const result: AnalysisResult = await analyzeVideo(videoPath, {
+        use_deepfilternet: useDeepfilternet,
+        use_normalize: useNormalize,
+        target_db: targetDb,
+        normalization_mode: peakNormalization ? "Peak" : "Rms",
+        legacy_threshold: legacyThreshold,
+        analyzer: {
+          threshold_db: thresholdDb,
+          min_duration_ms: minDurationMs,
+          left_buffer_sec: leftBufferSec,
+          right_buffer_sec: rightBufferSec,
+        },
+      });
 
       // 결과 변환 및 저장
       setSegments(result.segments);
@@ -372,16 +412,19 @@ function App() {
                         </Tooltip>
                       </label>
                       <span className="text-sm font-semibold tabular-nums">
-                        {thresholdDb} dB
+                        {thresholdDb} dBFS
                       </span>
                     </div>
                     <Slider
                       id="threshold"
-                      min={-60}
-                      max={60}
+                      min={-80}
+                      max={0}
                       step={1}
                       value={[thresholdDb]}
-                      onValueChange={(value) => setThresholdDb(value[0])}
+                      onValueChange={(value) => {
+                        setThresholdDb(value[0]);
+                        setLegacyThreshold(false);
+                      }}
                       className="w-full"
                       disabled={!videoPath || isAnalyzing}
                     />
@@ -6,17 +6,198 @@ export interface VideoInfo {
     fps: number;
 }
 
+export interface SegmentStats {
+    peak_db: number;
+    rms_db: number;
+    mean_relative_db: number;
+    confidence: number;
+}
+
 export interface Segment {
     start: number;
     end: number;
+    stats?: SegmentStats | null;
+}
+
+export type RegionKind = "Sound" | "Margin" | "Silence";
+
+export interface Region {
+    kind: RegionKind;
+    start: number;
+    end: number;
+    duration: number;
+}
+
+export interface VadOption {
+    low_hz: number;
+    high_hz: number;
+    min_band_ratio: number;
+    min_zero_crossing_hz: number;
+    max_zero_crossing_hz: number;
+}
+
+export type DetectorKind = "Rms" | { Vad: VadOption };
+
+export type ThresholdMode =
+    | "Fixed"
+    | {
+          Adaptive: {
+              percentile: number;
+              margin_db: number;
+              tracking_sec: number | null;
+          };
+      };
+
+export interface DeepFilterParameter {
+    post_filter: boolean;
+    post_filter_beta: number;
+    compensate_delay: boolean;
+    atten_lim_db: number;
+    min_db_thresh: number;
+    max_db_erb_thresh: number;
+    max_db_df_thresh: number;
+    reduce_mask: number;
+    workers: number;
+}
+
+export type DeepFilterPreset =
+    | "Gentle"
+    | "Default"
+    | "Aggressive"
+    | { Custom: Partial<DeepFilterParameter> };
+
+export interface BandPass {
+    low_hz: number;
+    high_hz: number;
+}
+
+export interface PreFilterOption {
+    high_pass_hz: number | null;
+    band_pass: BandPass | null;
+    notch_hz: number | null;
+}
+
+export interface CompressorOption {
+    threshold_db: number;
+    ratio: number;
+    attack_ms: number;
+    release_ms: number;
+    makeup_db: number;
+}
+
+export type NormalizationMode = "Peak" | "Rms" | "Loudness";
+
+export interface LoudnessReport {
+    integrated_lufs: number;
+    max_momentary_lufs: number;
+    max_short_term_lufs: number;
+    loudness_range_lu: number;
+    sample_peak_db: number;
+    true_peak_db: number;
+}
+
+export interface LimiterOption {
+    ceiling_db: number;
+    lookahead_ms: number;
+    release_ms: number;
+}
+
+export interface NormalizationReport {
+    gain_db: number;
+    limiter_reduction_db: number;
+    input: LoudnessReport;
+    output: LoudnessReport;
 }
 
 export interface AnalysisResult {
     segments: Segment[];
-    outputPath: string;
+    timeline: Region[];
+    duration: number;
+    threshold_db: number;
+    noise_floor_db: number | null;
+    loudness: NormalizationReport | null;
+    output_path: string | null;
+    artifacts: AudioArtifacts;
+}
+
+// 분석 설정에서 파일로 남기라고 한 오디오만 경로가 있다.
+export interface AudioArtifacts {
+    raw_path: string | null;
+    analysis_path: string | null;
+    denoised_path: string | null;
 }
 
+// 취소된 명령이 돌려주는 오류 메시지
+export const ANALYSIS_CANCELLED = "Cancelled";
+
+// 취소 토큰을 따로 가지는 명령 종류
+export type Operation = "Analysis" | "Normalization";
+
 export interface AnalysisProgress {
     phase: string;
     percentage: number;
 }
+
+export interface AudioAnalyzerOption {
+    detector: DetectorKind;
+    threshold_mode: ThresholdMode;
+    threshold_db: number;
+    close_threshold_db: number | null;
+    attack_ms: number;
+    hold_ms: number;
+    min_duration_ms: number;
+    min_silence_ms: number;
+    left_buffer_sec: number;
+    right_buffer_sec: number;
+    merge_gap_sec: number;
+    window_ms: number;
+    hop_ms: number;
+    pre_filter: PreFilterOption;
+    compressor: CompressorOption | null;
+}
+
+// 배치 작업의 모든 파일에 적용하는 분석 설정. 빠진 값은 기본값을 쓴다.
+export interface AnalysisSetting {
+    use_deepfilternet: boolean;
+    deepfilter_preset: DeepFilterPreset;
+    export_denoised: boolean;
+    export_raw: boolean;
+    export_analysis: boolean;
+    use_normalize: boolean;
+    target_db: number;
+    normalization_mode: NormalizationMode;
+    limiter: LimiterOption | null;
+    legacy_threshold: boolean;
+    analyzer: Partial<AudioAnalyzerOption>;
+}
+
+export interface BatchExport {
+    fps: string;
+    resolution: string;
+    output_dir: string | null;
+}
+
+export interface BatchJob {
+    job_id: number;
+    video_path: string;
+}
+
+export type JobStatus =
+    | "Queued"
+    | "Running"
+    | { Done: { result: AnalysisResult; fcpxml_path: string | null } }
+    | { Failed: string }
+    | "Cancelled";
+
+// "batch-job" 이벤트
+export interface JobEvent {
+    job_id: number;
+    status: JobStatus;
+}
+
+// "batch-progress" 이벤트
+export interface JobProgress {
+    job_id: number;
+    phase: string;
+    percentage: number;
+}
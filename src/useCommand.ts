@@ -1,5 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
-import { AnalysisResult, VideoInfo } from "./interface";
+import {
+  AnalysisResult,
+  AnalysisSetting,
+  BatchExport,
+  BatchJob,
+  Operation,
+  VideoInfo,
+} from "./interface";
 
 const useCommand = () => {
   const getVideoInfo = async (videoPath: string): Promise<VideoInfo> => {
@@ -7,27 +14,14 @@ const useCommand = () => {
     return videoInfo;
   };
 
+  // 비디오 하나를 분석한다. setting에서 빠진 값은 기본값을 쓴다.
   const analyzeVideo = async (
     videoPath: string,
-    useDeepfilternet: boolean,
-    thresholdDb: number,
-    minDurationMs: number,
-    leftBufferSec: number,
-    rightBufferSec: number,
-    useNormalize: boolean = false,
-    peakNormalization: boolean = false,
-    targetDb: number = -3.0,
+    setting?: Partial<AnalysisSetting>,
   ): Promise<AnalysisResult> => {
     const result = await invoke<AnalysisResult>("analyze_video", {
       videoPath,
-      useDeepfilternet,
-      thresholdDb,
-      minDurationMs,
-      leftBufferSec,
-      rightBufferSec,
-      useNormalize,
-      peakNormalization,
-      targetDb,
+      setting,
     });
     return result;
   };
@@ -38,6 +32,7 @@ const useCommand = () => {
     fps: string,
     resolution: string,
     outputPath: string,
+    connectedAudioPath?: string,
   ): Promise<string> => {
     const result = await invoke<string>("generate_fcpxml", {
       segments,
@@ -45,11 +40,46 @@ const useCommand = () => {
       fps,
       resolution,
       outputPath,
+      connectedAudioPath,
     });
     return result;
   };
 
-  return { getVideoInfo, analyzeVideo, generateFcpXml };
+  // 진행 중인 분석을 취소한다. 취소된 analyzeVideo는 ANALYSIS_CANCELLED로 reject된다.
+  // operation을 "Normalization"으로 주면 진행 중인 정규화를 취소한다.
+  const cancelAnalysis = async (operation: Operation = "Analysis"): Promise<void> => {
+    await invoke("cancel_analysis", { operation });
+  };
+
+  // 여러 비디오를 백그라운드에서 분석한다. 진행 상황은 "batch-job", "batch-progress" 이벤트로 받는다.
+  const startBatch = async (
+    videoPaths: string[],
+    setting?: Partial<AnalysisSetting>,
+    concurrency?: number,
+    exportSetting?: BatchExport,
+  ): Promise<BatchJob[]> => {
+    const jobs = await invoke<BatchJob[]>("start_batch", {
+      videoPaths,
+      setting,
+      concurrency,
+      export: exportSetting,
+    });
+    return jobs;
+  };
+
+  // jobIds가 없으면 남은 배치 작업을 모두 취소한다.
+  const cancelJobs = async (jobIds?: number[]): Promise<void> => {
+    await invoke("cancel_jobs", { jobIds });
+  };
+
+  return {
+    getVideoInfo,
+    analyzeVideo,
+    generateFcpXml,
+    cancelAnalysis,
+    startBatch,
+    cancelJobs,
+  };
 };
 
 export default useCommand;